@@ -0,0 +1,19 @@
+extern crate lifegame;
+
+use std::time::Instant;
+use lifegame::*;
+
+fn main() {
+    let mut game = LifeGame::new(4096, 4096);
+    game.reset_by_rand();
+
+    let rounds = 20;
+    let start = Instant::now();
+    let mut num_cells = 0;
+    for _ in 0..rounds {
+        num_cells = game.num_cells();
+    }
+    let elapsed = start.elapsed();
+
+    println!("count_live 4096x4096 ({} live): {:?} per call", num_cells, elapsed / rounds);
+}
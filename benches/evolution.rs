@@ -0,0 +1,18 @@
+extern crate lifegame;
+
+use std::time::Instant;
+use lifegame::*;
+
+fn main() {
+    let mut game = LifeGame::new(1024, 1024);
+    game.reset_by_rand();
+
+    let rounds = 20;
+    let start = Instant::now();
+    for _ in 0..rounds {
+        game.evolution();
+    }
+    let elapsed = start.elapsed();
+
+    println!("evolution 1024x1024 ({} live): {:?} per generation", game.num_cells(), elapsed / rounds);
+}
@@ -1,3 +1,12 @@
-pub mod lifegame;
-
-pub use lifegame::*;
+pub mod lifegame;
+pub mod patterns;
+pub mod sparse;
+#[cfg(feature = "send-callbacks")]
+pub mod shared;
+pub mod timeline;
+
+pub use lifegame::*;
+pub use sparse::*;
+#[cfg(feature = "send-callbacks")]
+pub use shared::*;
+pub use timeline::*;
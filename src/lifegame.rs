@@ -1,21 +1,118 @@
 extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "image")]
+extern crate image;
 
 use std::fmt;
-use self::rand::Rng;
+use std::error;
+use std::convert::TryFrom;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use self::rand::{Rng, SeedableRng, StdRng};
+#[cfg(feature = "rayon")]
+use self::rayon::prelude::*;
+#[cfg(feature = "image")]
+use std::path::Path;
+
+// Callbacks make LifeGame neither Send nor Sync. With the `send-callbacks`
+// feature they must be Send, which makes LifeGame Send (still not Sync).
+#[cfg(not(feature = "send-callbacks"))]
+pub trait CallbackFn: FnMut(CallbackInfo) + 'static {}
+#[cfg(not(feature = "send-callbacks"))]
+impl<F: FnMut(CallbackInfo) + 'static> CallbackFn for F {}
+#[cfg(not(feature = "send-callbacks"))]
+type Callback = Box<dyn FnMut(CallbackInfo)>;
+
+#[cfg(feature = "send-callbacks")]
+pub trait CallbackFn: FnMut(CallbackInfo) + Send + 'static {}
+#[cfg(feature = "send-callbacks")]
+impl<F: FnMut(CallbackInfo) + Send + 'static> CallbackFn for F {}
+#[cfg(feature = "send-callbacks")]
+type Callback = Box<dyn FnMut(CallbackInfo) + Send>;
+
+struct RegionCallback {
+    id: CallbackId,
+    rect: (usize, usize, usize, usize),
+    callback: Callback,
+}
+
+// The board as seen by `scoped_run`'s body. Events raised by a change made
+// through it reach the scoped callback before the change returns.
+pub struct ScopedRun<'g, F: FnMut(CallbackInfo)> {
+    game: &'g mut LifeGame,
+    callback: F,
+    was_scoped: bool,
+}
+
+impl<'g, F: FnMut(CallbackInfo)> ScopedRun<'g, F> {
+    pub fn with<M, R>(&mut self, f: M) -> R
+        where M: FnOnce(&mut LifeGame) -> R {
+        let result = f(self.game);
+        for info in mem::take(&mut self.game.pending) {
+            (self.callback)(info);
+        }
+        result
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, live: bool) -> &mut Self {
+        self.with(|game| { game.set(x, y, live); });
+        self
+    }
+
+    pub fn evolution(&mut self) -> &mut Self {
+        self.with(|game| { game.evolution(); });
+        self
+    }
+
+    pub fn game(&self) -> &LifeGame {
+        self.game
+    }
+}
+
+// Unmutes the installed callback, including while unwinding.
+impl<'g, F: FnMut(CallbackInfo)> Drop for ScopedRun<'g, F> {
+    fn drop(&mut self) {
+        self.game.scoped = self.was_scoped;
+        self.game.pending.clear();
+    }
+}
 
 pub struct LifeGame {
     generation: usize,
-    world :Vec<u8>,
+    world: Arc<Vec<u8>>,
     width: usize,
     height: usize,
-    callback: Box<FnMut(CallbackInfo)>,
+    callback: Callback,
+    prev_num_cells: usize,
+    report_neighbors: bool,
+    population_range: Option<(usize, usize)>,
+    walls: HashSet<(usize, usize)>,
+    radius: usize,
+    running: bool,
+    count_cache: Option<(Arc<Vec<u8>>, Vec<u8>)>,
+    labels: HashMap<usize, String>,
+    dirty_rect: Option<(usize, usize, usize, usize)>,
+    noise: f64,
+    rng: Option<StdRng>,
+    watched: Option<(usize, usize)>,
+    cell_timeline: Vec<bool>,
+    region_callbacks: Vec<RegionCallback>,
+    next_callback_id: usize,
+    scoped: bool,
+    pending: Vec<CallbackInfo>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct CellInfo {
     pub x: usize,
     pub y: usize,
-    pub live: bool
+    pub live: bool,
+    pub neighbors: Option<u8>
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -32,7 +129,42 @@ pub struct CallbackInfo {
     pub width: usize,
     pub height: usize,
     pub num_cells: usize,
-    pub cell: Option<CellInfo>
+    pub cell: Option<CellInfo>,
+    pub label: Option<String>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CallbackId(usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Transition {
+    Birth,
+    Survive,
+    Underpopulation,
+    Overpopulation,
+    StayDead,
+    // Walls keep their state whatever their neighbors are.
+    Wall
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellTransition {
+    pub x: usize,
+    pub y: usize,
+    pub live: bool,
+    pub neighbors: u8,
+    pub transition: Transition
+}
+
+// `horizontal` is a left-right mirror and `vertical` a top-bottom mirror.
+// `diagonal` is a reflection across the main diagonal and is only set on
+// square boards.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SymmetrySet {
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub rotation_180: bool,
+    pub diagonal: bool
 }
 
 pub struct LifeGameIterBool<'a> {
@@ -46,17 +178,193 @@ pub struct LifeGameIterU8<'a> {
     pos: usize,
     max: usize,
     live: Option<bool>,
+    counts: Vec<u8>,
     game: &'a LifeGame
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum DimensionError {
+    Zero,
+    LengthMismatch { width: usize, height: usize, len: usize },
+    SizeMismatch { width: usize, height: usize, other_width: usize, other_height: usize }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    InvalidOffset { line: usize },
+    InvalidCell { line: usize, cell: char },
+    UnsupportedLine { line: usize },
+    TooLarge,
+    Empty
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    TrailingData,
+    TooLarge,
+    InvalidBase64,
+    Dimension(DimensionError)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchError {
+    TooLarge { cells: usize }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayError {
+    SizeMismatch { index: usize, width: usize, height: usize },
+    UnreplayableReset { index: usize, num_cells: usize }
+}
+
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub enum ImageError {
+    Image(image::ImageError),
+    Dimension(DimensionError)
+}
+
+#[derive(Debug)]
+pub enum RleError {
+    Io(io::Error),
+    MissingHeader,
+    InvalidHeader { line: usize },
+    InvalidToken { line: usize, token: char },
+    OutOfBounds { line: usize },
+    UnsupportedRule { line: usize, rule: String }
+}
+
+// Coordinates are handled as `isize` while counting neighbors.
+pub const MAX_DIMENSION: usize = isize::MAX as usize;
+
+const BYTES_MAGIC: &[u8] = b"LIFE";
+const BYTES_VERSION: u8 = 1;
+const BYTES_HEADER_LEN: usize = 4 + 1 + (8 * 3);
+
+const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0),          (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
+
+// Neighbor counts are held in a `u16`: (2 * 127 + 1)^2 - 1 = 65024.
+pub const MAX_NEIGHBORHOOD_RADIUS: usize = 127;
+
+// Boards up to this many cells are searched exhaustively by `predecessors`.
+pub const PREDECESSOR_MAX_CELLS: usize = 16;
+
+// The only rule the board runs; written into RLE headers.
+const RLE_RULE: &str = "B3/S23";
+const RLE_LINE_LEN: usize = 70;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ (byte as u64)).wrapping_mul(FNV_PRIME))
+}
+
+// URL-safe alphabet (RFC 4648 section 5) without padding.
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &byte)| n | ((byte as u32) << (16 - (8 * i))));
+        for i in 0..=chunk.len() {
+            s.push(BASE64_ALPHABET[((n >> (18 - (6 * i))) & 0x3f) as usize] as char);
+        }
+    }
+    s
+}
+
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let digits = s.bytes()
+        .map(|c| BASE64_ALPHABET.iter().position(|&a| a == c).map(|d| d as u32))
+        .collect::<Option<Vec<u32>>>()?;
+    let mut bytes = Vec::with_capacity((digits.len() * 3) / 4);
+    for chunk in digits.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &d)| n | (d << (18 - (6 * i))));
+        for i in 0..(chunk.len() - 1) {
+            bytes.push((n >> (16 - (8 * i))) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+// Escapes a value written inside a double-quoted XML attribute.
+fn escape_xml_attr(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('"', "&quot;")
+}
+
+// Returns None instead of aborting when the world cannot be allocated.
+fn try_alloc_world(len: usize) -> Option<Vec<u8>> {
+    let mut world = Vec::new();
+    world.try_reserve_exact(len).ok()?;
+    world.resize(len, 0);
+    Some(world)
+}
+
+// Counts above 255 (neighborhood radius 8 and up) are reported as 255
+// through the `u8` APIs.
+fn saturate_u8(count: u16) -> u8 {
+    count.min(u8::MAX as u16) as u8
+}
+
+#[cfg(not(feature = "simd"))]
+fn count_live(world: &[u8]) -> usize {
+    count_live_scalar(world)
+}
+
+fn count_live_scalar(world: &[u8]) -> usize {
+    world.iter().filter(|&&cell| cell > 0).count()
+}
+
+// Counts 8 cells at a time: the high bit of each byte of `mask` is set
+// when that byte of `word` is non-zero.
+#[cfg(feature = "simd")]
+fn count_live(world: &[u8]) -> usize {
+    const LOW7: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+    const HIGH: u64 = 0x8080_8080_8080_8080;
+
+    let chunks = world.chunks_exact(8);
+    let rest = count_live_scalar(chunks.remainder());
+    chunks.fold(rest, |count, chunk| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(chunk);
+        let word = u64::from_ne_bytes(bytes);
+        let mask = (((word & LOW7) + LOW7) | word) & HIGH;
+        count + (mask.count_ones() as usize)
+    })
+}
+
 impl LifeGame {
     pub fn new(width: usize, height: usize) -> LifeGame {
         if (width == 0) || (height == 0) {
             panic!("Width or height must be not 0.");
         }
+        if (width > MAX_DIMENSION) || (height > MAX_DIMENSION) {
+            panic!("Width or height must be not over MAX_DIMENSION.");
+        }
+
+        LifeGame::with_dimensions(width, height)
+    }
+
+    // A 0x0 board, where `get` is always dead and `set` and every evolution
+    // method do nothing.
+    pub fn empty() -> LifeGame {
+        LifeGame::with_dimensions(0, 0)
+    }
 
-        let len = width * height;
-        let world = vec![0; len];
+    fn with_dimensions(width: usize, height: usize) -> LifeGame {
+        let len = width.checked_mul(height).expect("Width * height is too large.");
+        let world = Arc::new(vec![0; len]);
 
         LifeGame {
             generation: 0,
@@ -64,821 +372,4434 @@ impl LifeGame {
             width,
             height,
             callback: Box::new(|_| {}),
+            prev_num_cells: 0,
+            report_neighbors: false,
+            population_range: None,
+            walls: HashSet::new(),
+            radius: 1,
+            running: true,
+            count_cache: None,
+            labels: HashMap::new(),
+            dirty_rect: None,
+            noise: 0.0,
+            rng: None,
+            watched: None,
+            cell_timeline: Vec::new(),
+            region_callbacks: Vec::new(),
+            next_callback_id: 0,
+            scoped: false,
+            pending: Vec::new(),
         }
     }
 
-    fn xy2i(&self, x: usize, y: usize) -> usize {
-        (self.width * y) + x
+    // `aspect` is width / height. Both dimensions are at least 1.
+    pub fn new_for_cells(target_cells: usize, aspect: f64) -> LifeGame {
+        if !aspect.is_finite() || (aspect <= 0.0) {
+            panic!("Aspect must be a positive number.");
+        }
+        let height = ((target_cells as f64) / aspect).sqrt().round().max(1.0);
+        let width = (height * aspect).round().max(1.0);
+        LifeGame::new(width as usize, height as usize)
     }
 
-    fn get_as_u8(&self, x: usize, y: usize) -> u8 {
-        let i = self.xy2i(x, y);
-        self.world[i]
+    // Like `new`, but returns None when the dimensions are invalid or the
+    // world cannot be allocated.
+    fn try_with_dimensions(width: usize, height: usize) -> Option<LifeGame> {
+        if (width == 0) || (height == 0) || (width > MAX_DIMENSION) || (height > MAX_DIMENSION) {
+            return None;
+        }
+        let world = try_alloc_world(width.checked_mul(height)?)?;
+        let mut game = LifeGame::new(1, 1);
+        game.world = Arc::new(world);
+        game.width = width;
+        game.height = height;
+        Some(game)
     }
 
-    pub fn get(&self, x: usize, y: usize) -> bool {
-        let live = self.get_as_u8(x, y);
-        live > 0
+    pub fn new_with_world(width: usize, height: usize, mut world: Vec<u8>) -> Result<LifeGame, DimensionError> {
+        if (width == 0) || (height == 0) {
+            return Err(DimensionError::Zero);
+        }
+        if width.checked_mul(height) != Some(world.len()) {
+            return Err(DimensionError::LengthMismatch { width, height, len: world.len() });
+        }
+
+        for cell in world.iter_mut() {
+            if *cell > 1 {
+                *cell = 1;
+            }
+        }
+        let mut game = LifeGame::new(width, height);
+        game.world = Arc::new(world);
+        Ok(game)
     }
 
-    fn set_u8(&mut self, x: usize, y: usize, live: u8) {
-        let i = self.xy2i(x, y);
-        self.world[i] = live;
+    pub fn from_bool_slice(width: usize, height: usize, data: &[bool]) -> Result<LifeGame, DimensionError> {
+        let world = data.iter().map(|&live| live as u8).collect();
+        LifeGame::new_with_world(width, height, world)
     }
 
-    pub fn set(&mut self, x: usize, y: usize, live: bool) -> &Self {
-        let live = if live { 1 } else { 0 };
-        self.set_u8(x, y, live);
-        self.on_set(x, y, live);
-        self
+    // Layout: "LIFE", version, width, height and generation as u64 LE,
+    // then the cells in row-major order packed 8 per byte (LSB first).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BYTES_HEADER_LEN + self.world.len().div_ceil(8));
+        bytes.extend_from_slice(BYTES_MAGIC);
+        bytes.push(BYTES_VERSION);
+        bytes.extend_from_slice(&(self.width as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.generation as u64).to_le_bytes());
+        for chunk in self.world.chunks(8) {
+            let packed = chunk.iter().enumerate()
+                .fold(0u8, |byte, (bit, &cell)| if cell > 0 { byte | (1 << bit) } else { byte });
+            bytes.push(packed);
+        }
+        bytes
     }
 
-    pub fn width(&self) -> usize {
-        self.width
+    pub fn from_bytes(bytes: &[u8]) -> Result<LifeGame, DecodeError> {
+        if bytes.len() < BYTES_HEADER_LEN {
+            return Err(if bytes.starts_with(BYTES_MAGIC) { DecodeError::Truncated } else { DecodeError::InvalidMagic });
+        }
+        if &bytes[0..4] != BYTES_MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+        if bytes[4] != BYTES_VERSION {
+            return Err(DecodeError::UnsupportedVersion(bytes[4]));
+        }
+
+        let read_u64 = |at: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[at..(at + 8)]);
+            u64::from_le_bytes(buf)
+        };
+        let width = usize::try_from(read_u64(5)).map_err(|_| DecodeError::TooLarge)?;
+        let height = usize::try_from(read_u64(13)).map_err(|_| DecodeError::TooLarge)?;
+        let generation = usize::try_from(read_u64(21)).map_err(|_| DecodeError::TooLarge)?;
+        let len = width.checked_mul(height).ok_or(DecodeError::TooLarge)?;
+
+        let cells = &bytes[BYTES_HEADER_LEN..];
+        if cells.len() < len.div_ceil(8) {
+            return Err(DecodeError::Truncated);
+        }
+        if cells.len() > len.div_ceil(8) {
+            return Err(DecodeError::TrailingData);
+        }
+
+        let world = (0..len).map(|i| (cells[i / 8] >> (i % 8)) & 1).collect();
+        let mut game = LifeGame::new_with_world(width, height, world).map_err(DecodeError::Dimension)?;
+        game.generation = generation;
+        Ok(game)
     }
 
-    pub fn height(&self) -> usize {
-        self.height
+    pub fn to_base64(&self) -> String {
+        base64_encode(&self.to_bytes())
     }
 
-    fn coordinate_normalize(n: isize, max: usize) -> usize {
-        if n < 0 {
-            ((max as isize) + n) as usize
-        } else {
-            let n = n as usize;
-            if n >= max {
-                n - max
+    pub fn from_base64(s: &str) -> Result<LifeGame, DecodeError> {
+        let bytes = base64_decode(s).ok_or(DecodeError::InvalidBase64)?;
+        LifeGame::from_bytes(&bytes)
+    }
+
+    // Pixels darker than `threshold` are live.
+    #[cfg(feature = "image")]
+    pub fn from_image(path: &Path, threshold: u8) -> Result<LifeGame, ImageError> {
+        let luma = image::open(path).map_err(ImageError::Image)?.to_luma8();
+        let (width, height) = (luma.width() as usize, luma.height() as usize);
+        let world = luma.pixels().map(|pixel| (pixel.0[0] < threshold) as u8).collect();
+        LifeGame::new_with_world(width, height, world).map_err(ImageError::Dimension)
+    }
+
+    pub fn from_life105(s: &str) -> Result<LifeGame, ParseError> {
+        let mut blocks: Vec<(isize, isize, usize, Vec<Vec<bool>>)> = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with("#Life") || line.starts_with("#D") || line.starts_with("#N") {
+                continue;
+            }
+
+            if let Some(offset) = line.strip_prefix("#P") {
+                let offset: Vec<isize> = offset
+                    .split_whitespace()
+                    .map(|n| n.parse().map_err(|_| ParseError::InvalidOffset { line: line_no }))
+                    .collect::<Result<_, _>>()?;
+                if offset.len() != 2 {
+                    return Err(ParseError::InvalidOffset { line: line_no });
+                }
+                blocks.push((offset[0], offset[1], line_no, Vec::new()));
+            } else if let Some(rule) = line.strip_prefix("#R") {
+                if rule.trim() != "23/3" {
+                    return Err(ParseError::UnsupportedLine { line: line_no });
+                }
+            } else if line.starts_with('#') {
+                return Err(ParseError::UnsupportedLine { line: line_no });
             } else {
-                n
+                let row = line
+                    .chars()
+                    .map(|cell| match cell {
+                        '*' => Ok(true),
+                        '.' => Ok(false),
+                        _   => Err(ParseError::InvalidCell { line: line_no, cell })
+                    })
+                    .collect::<Result<Vec<bool>, _>>()?;
+                if blocks.is_empty() {
+                    blocks.push((0, 0, line_no, Vec::new()));
+                }
+                blocks.last_mut().unwrap().3.push(row);
             }
         }
-    }
 
-    fn neighbors_lives(&self, x: usize, y: usize) -> u8 {
-        let x = x as isize;
-        let y = y as isize;
-        let width = self.width();
-        let height = self.height();
-
-        let mut count: u8 = 0;
-        for j in (y-1)..(y+2) {
-            for i in (x-1)..(x+2) {
-                let i = LifeGame::coordinate_normalize(i, width);
-                let j = LifeGame::coordinate_normalize(j, height);
-                if self.get(i, j) {
-                    count += 1;
+        let blocks: Vec<_> = blocks.into_iter().filter(|(_, _, _, rows)| !rows.is_empty()).collect();
+        let mut extent: Option<(isize, isize, isize, isize)> = None;
+        for &(x, y, line, ref rows) in blocks.iter() {
+            let w = rows.iter().map(|row| row.len()).max().unwrap_or(0) as isize;
+            let h = rows.len() as isize;
+            let (x1, y1) = match (x.checked_add(w), y.checked_add(h)) {
+                (Some(x1), Some(y1)) => (x1, y1),
+                _ => return Err(ParseError::InvalidOffset { line })
+            };
+            extent = Some(match extent {
+                None => (x, y, x1, y1),
+                Some((l, t, r, b)) => (l.min(x), t.min(y), r.max(x1), b.max(y1))
+            });
+        }
+        let (left, top, right, bottom) = extent.ok_or(ParseError::Empty)?;
+        if (right <= left) || (bottom <= top) {
+            return Err(ParseError::Empty);
+        }
+
+        // Checked before allocating, so a far away block is an error and not
+        // an abort.
+        let size = |from: isize, to: isize| to.checked_sub(from).map(|n| n as usize).filter(|&n| n <= MAX_DIMENSION);
+        let (width, height) = match (size(left, right), size(top, bottom)) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return Err(ParseError::TooLarge)
+        };
+        let mut game = LifeGame::try_with_dimensions(width, height).ok_or(ParseError::TooLarge)?;
+        for (bx, by, _, rows) in blocks {
+            for (dy, row) in rows.iter().enumerate() {
+                for (dx, &live) in row.iter().enumerate() {
+                    if live {
+                        let x = (bx - left) as usize + dx;
+                        let y = (by - top) as usize + dy;
+                        game.set_u8(x, y, 1);
+                    }
                 }
             }
         }
-        if self.get(x as usize, y as usize) {
-            count -= 1;
-        }
-        count
+        Ok(game)
     }
 
-    fn cell_evolution(&self, x: usize, y: usize) -> u8 {
-        let live = self.get(x, y);
-        let count = self.neighbors_lives(x, y);
+    pub fn from_rle(s: &str) -> Result<LifeGame, RleError> {
+        LifeGame::from_rle_reader(s.as_bytes())
+    }
 
-        if live {
-            match count {
-                2 | 3 => 1,
-                0 | 1 => 0,
-                _     => 0
+    pub fn from_rle_reader<R: BufRead>(mut reader: R) -> Result<LifeGame, RleError> {
+        let mut line = String::new();
+        let mut line_no = 0;
+
+        let mut game = loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(RleError::Io)? == 0 {
+                return Err(RleError::MissingHeader);
             }
-        } else {
-            match count {
-                3 => 1,
-                _ => 0
+            line_no += 1;
+            let header = line.trim();
+            if header.is_empty() || header.starts_with('#') {
+                continue;
             }
-        }
-    }
+            let (width, height, rule) = LifeGame::parse_rle_header(header)
+                .ok_or(RleError::InvalidHeader { line: line_no })?;
+            if let Some(rule) = rule {
+                if !LifeGame::is_rle_rule_supported(&rule) {
+                    return Err(RleError::UnsupportedRule { line: line_no, rule });
+                }
+            }
+            break LifeGame::try_with_dimensions(width, height)
+                .ok_or(RleError::InvalidHeader { line: line_no })?;
+        };
 
-    pub fn evolution(&mut self) -> &Self {
-        let mut new = LifeGame::new(self.width, self.height);
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let live = self.cell_evolution(x, y);
-                new.set_u8(x, y, live);
+        let (mut x, mut y, mut run): (usize, usize, usize) = (0, 0, 0);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(RleError::Io)? == 0 {
+                break;
+            }
+            line_no += 1;
+            for token in line.chars() {
+                if let Some(digit) = token.to_digit(10) {
+                    run = run.checked_mul(10)
+                        .and_then(|run| run.checked_add(digit as usize))
+                        .ok_or(RleError::OutOfBounds { line: line_no })?;
+                    continue;
+                }
+                let count = if run == 0 { 1 } else { run };
+                run = 0;
+                match token {
+                    'b' | '.' => x = x.saturating_add(count),
+                    'o' => {
+                        let end = x.saturating_add(count);
+                        if (y >= game.height) || (end > game.width) {
+                            return Err(RleError::OutOfBounds { line: line_no });
+                        }
+                        for i in x..end {
+                            game.set_u8(i, y, 1);
+                        }
+                        x = end;
+                    }
+                    '$' => {
+                        x = 0;
+                        y = y.saturating_add(count);
+                    }
+                    '!' => return Ok(game),
+                    _ if token.is_whitespace() => {}
+                    _ => return Err(RleError::InvalidToken { line: line_no, token })
+                }
             }
         }
-        self.world = new.world;
-        self.generation = self.generation() + 1;
-        self.on_evolution();
-        self
+        Ok(game)
     }
 
-    fn update_to_neighbors_lives(&mut self) -> &Self {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.get(x, y) {
-                    let lives = self.neighbors_lives(x, y);
-                    self.set_u8(x, y, lives);
+    pub fn to_rle(&self) -> String {
+        let mut items: Vec<(usize, char)> = Vec::new();
+        let mut rows = 0;
+        for row in self.world.chunks(self.width.max(1)) {
+            let mut runs: Vec<(usize, char)> = Vec::new();
+            for &cell in row.iter() {
+                let tag = if cell > 0 { 'o' } else { 'b' };
+                match runs.last_mut() {
+                    Some(&mut (ref mut count, last)) if last == tag => *count += 1,
+                    _ => runs.push((1, tag))
                 }
             }
+            if runs.last().map(|&(_, tag)| tag) == Some('b') {
+                runs.pop();
+            }
+            if runs.is_empty() {
+                rows += 1;
+                continue;
+            }
+            if rows > 0 {
+                items.push((rows, '$'));
+            }
+            items.extend(runs);
+            rows = 1;
         }
-        self
+        items.push((1, '!'));
+
+        let mut rle = format!("x = {}, y = {}, rule = {}\n", self.width, self.height, RLE_RULE);
+        let mut line = String::new();
+        for (count, tag) in items {
+            let item = if count > 1 { format!("{}{}", count, tag) } else { tag.to_string() };
+            if line.len() + item.len() > RLE_LINE_LEN {
+                rle.push_str(&line);
+                rle.push('\n');
+                line.clear();
+            }
+            line.push_str(&item);
+        }
+        rle.push_str(&line);
+        rle.push('\n');
+        rle
     }
 
-    pub fn reset(&mut self) -> &Self {
-        let len = self.width * self.height;
-        self.world = vec![0; len];
-        self.generation = 0;
-        self.on_reset();
-        self
+    // Line breaks in `name` become spaces; each line of `comment` gets its
+    // own #C line.
+    pub fn to_rle_with_meta(&self, name: &str, comment: &str) -> String {
+        let name = name.replace(['\r', '\n'], " ");
+        let mut rle = format!("#N {}\n", name);
+        for line in comment.lines() {
+            rle.push_str(&format!("#C {}\n", line));
+        }
+        rle.push_str(&self.to_rle());
+        rle
     }
 
-    pub fn reset_by_rand(&mut self) -> &Self {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let live =
-                    if rand::thread_rng().gen_range(0, 100) > 50 {
-                        1
-                    } else {
-                        0
-                    };
-                self.set_u8(x, y, live);
+    fn parse_rle_header(header: &str) -> Option<(usize, usize, Option<String>)> {
+        let (mut width, mut height, mut rule): (Option<usize>, Option<usize>, _) = (None, None, None);
+        for item in header.split(',') {
+            let mut pair = item.splitn(2, '=');
+            let key = pair.next()?.trim();
+            let value = pair.next()?.trim();
+            match key {
+                "x" => width = value.parse().ok(),
+                "y" => height = value.parse().ok(),
+                "rule" => rule = Some(value.to_string()),
+                _ => {}
             }
         }
-        self.generation = 0;
-        self.on_reset();
-        self
+        match (width, height) {
+            (Some(width), Some(height)) if (width > 0) && (height > 0)
+                && (width <= MAX_DIMENSION) && (height <= MAX_DIMENSION)
+                && width.checked_mul(height).is_some() => Some((width, height, rule)),
+            _ => None
+        }
     }
 
-    pub fn generation(&self) -> usize {
-        self.generation
+    // The board only runs Conway's rule, written as B3/S23 or 23/3.
+    fn is_rle_rule_supported(rule: &str) -> bool {
+        let rule = rule.to_ascii_uppercase();
+        (rule == RLE_RULE) || (rule == "S23/B3") || (rule == "23/3")
     }
 
-    pub fn set_callback<F>(mut self, callback: F) -> Self
-        where F: FnMut(CallbackInfo) + 'static {
-        self.callback = Box::new(callback);
-        self
+    fn xy2i(&self, x: usize, y: usize) -> usize {
+        (self.width * y) + x
     }
 
-    fn on_reset(&mut self) {
-        let num_cells = self.num_cells();
-        (self.callback)(
-            CallbackInfo {
-                event: CallbackEvent::Reset,
-                generation: self.generation,
-                width: self.width,
-                height: self.height,
-                num_cells: num_cells,
-                cell: None
-            });
+    fn get_as_u8(&self, x: usize, y: usize) -> u8 {
+        let i = self.xy2i(x, y);
+        self.world[i]
     }
 
-    fn on_set(&mut self, x: usize, y: usize, live: u8) {
-        let live = if live == 1 { true } else { false };
-        let num_cells = self.num_cells();
-        (self.callback)(
-            CallbackInfo {
-                event: CallbackEvent::Set,
-                generation: self.generation,
-                width: self.width,
-                height: self.height,
-                num_cells: num_cells,
-                cell: Some(CellInfo { x, y, live })
-            });
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let live = self.get_as_u8(x, y);
+        live > 0
     }
 
-    fn on_evolution(&mut self) {
-        let num_cells = self.num_cells();
-        (self.callback)(
-            CallbackInfo {
-                event: CallbackEvent::Evolution,
-                generation: self.generation,
-                width: self.width,
-                height: self.height,
-                num_cells: num_cells,
-                cell: None
-            });
+    fn set_u8(&mut self, x: usize, y: usize, live: u8) {
+        let i = self.xy2i(x, y);
+        Arc::make_mut(&mut self.world)[i] = live;
     }
 
-    pub fn num_cells(&self) -> usize {
-        self.world.iter().fold(0, |sum, &live| sum + (live as usize))
+    // Stores `value` as is, e.g. to build boards holding neighbor counts.
+    #[cfg(test)]
+    fn debug_set_raw(&mut self, x: usize, y: usize, value: u8) -> &Self {
+        self.set_u8(x, y, value);
+        self
     }
 
-    pub fn iter(&self, live: Option<bool>) -> LifeGameIterBool {
-        let iter = LifeGameIterBool {
-                        pos: 0,
-                        max: self.width() * self.height(),
-                        live: live,
-                        game: self
-                    };
-        iter
+    pub fn set(&mut self, x: usize, y: usize, live: bool) -> &Self {
+        if self.is_empty() {
+            return self;
+        }
+        if self.population_range.is_none() {
+            let num_cells = self.num_cells();
+            self.observe_population(num_cells);
+        }
+        let live = if live { 1 } else { 0 };
+        self.set_u8(x, y, live);
+        debug_assert!(self.is_binary());
+        self.on_set(x, y, live);
+        self
     }
 
-    pub fn iter_as_u8(&mut self, live: Option<bool>) -> LifeGameIterU8 {
-        self.update_to_neighbors_lives();
-        let iter = LifeGameIterU8 {
-                        pos: 0,
-                        max: self.width() * self.height(),
-                        live: live,
-                        game: self
-                    };
-        iter
+    pub fn clear_region(&mut self, x0: usize, y0: usize, x1: usize, y1: usize) -> &Self {
+        if (x0 > x1) || (y0 > y1) || (x1 >= self.width) || (y1 >= self.height) {
+            panic!("Region must be inside the world.");
+        }
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if self.get(x, y) {
+                    self.set(x, y, false);
+                }
+            }
+        }
+        self
     }
-}
 
-impl fmt::Display for LifeGame {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let summary = format!("({}, {})", self.width, self.height);
-
-        let mut world = String::new();
+    pub fn for_each_mut<F>(&mut self, mut f: F) -> &Self
+        where F: FnMut(usize, usize, &mut bool) {
+        let mut world = vec![0; self.world.len()];
         for y in 0..self.height {
             for x in 0..self.width {
-                //let live = self.get(x, y);
-                //let cell = if live { "o " } else { "x " };
-                let cell = &self.get_as_u8(x, y).to_string();
-                world.push_str(cell);
+                let mut live = self.get(x, y);
+                f(x, y, &mut live);
+                world[self.xy2i(x, y)] = if live { 1 } else { 0 };
             }
-            world.push_str("\n");
         }
-
-        write!(f, "{}\n{}", summary, world)
+        self.world = Arc::new(world);
+        self.on_reset();
+        self
     }
-}
 
-impl<'a> Iterator for LifeGameIterBool<'a> {
-    type Item = (usize, usize, bool);
-    fn next (&mut self) -> Option<(usize, usize, bool)> {
-        loop {
-            if self.pos >= self.max {
-                return None;
+    pub fn shift(&mut self, dx: isize, dy: isize, wrap: bool) -> Vec<(usize, usize)> {
+        let mut world = vec![0; self.world.len()];
+        let mut lost = Vec::new();
+        for (x, y, _) in self.iter(Some(true)) {
+            let nx = (x as isize) + dx;
+            let ny = (y as isize) + dy;
+            if wrap {
+                let nx = LifeGame::normalize_coord(nx, self.width);
+                let ny = LifeGame::normalize_coord(ny, self.height);
+                world[self.xy2i(nx, ny)] = 1;
+            } else if (nx < 0) || (ny < 0) || (nx as usize >= self.width) || (ny as usize >= self.height) {
+                lost.push((x, y));
+            } else {
+                world[self.xy2i(nx as usize, ny as usize)] = 1;
             }
+        }
+        self.world = Arc::new(world);
+        self.on_reset();
+        lost
+    }
 
-            let pos = self.pos;
-            self.pos += 1;
+    // Reflects the cells across the main diagonal.
+    // A non-square board swaps its width and height.
+    pub fn transpose(&mut self) -> &Self {
+        let (width, height) = (self.height, self.width);
+        let mut world = vec![0; self.world.len()];
+        for (x, y, live) in self.iter(Some(true)) {
+            world[(width * x) + y] = live as u8;
+        }
+        self.world = Arc::new(world);
+        self.width = width;
+        self.height = height;
+        self.walls = self.walls.iter().map(|&(x, y)| (y, x)).collect();
+        self.watched = self.watched.map(|(x, y)| (y, x));
+        self.on_reset();
+        self
+    }
 
-            let live = self.game.world[pos] > 0;
-            if (self.live == None) || (self.live == Some(live)) {
-                let x = pos % self.game.width();
-                let y = pos / self.game.width();
+    // Revives every dead cell with at least one live neighbor.
+    pub fn dilate(&mut self) -> &Self {
+        let counts = self.neighbor_counts();
+        let world = self.world.iter().zip(counts.iter())
+            .map(|(&live, &count)| if (live > 0) || (count > 0) { 1 } else { 0 })
+            .collect();
+        self.world = Arc::new(world);
+        self.on_reset();
+        self
+    }
 
-                return Some((x, y, live));
-            }
+    // Kills every live cell with fewer than 8 live neighbors.
+    pub fn erode(&mut self) -> &Self {
+        let counts = self.neighbor_counts();
+        let world = self.world.iter().zip(counts.iter())
+            .map(|(&live, &count)| if (live > 0) && (count >= 8) { 1 } else { 0 })
+            .collect();
+        self.world = Arc::new(world);
+        self.on_reset();
+        self
+    }
+
+    // Walls keep their stored state through evolution.
+    pub fn set_wall(&mut self, x: usize, y: usize, wall: bool) -> &Self {
+        if (x >= self.width) || (y >= self.height) {
+            panic!("Wall must be inside the world.");
+        }
+        if wall {
+            self.walls.insert((x, y));
+        } else {
+            self.walls.remove(&(x, y));
         }
+        self
     }
-}
 
-impl<'a> Iterator for LifeGameIterU8<'a> {
-    type Item = (usize, usize, u8);
-    fn next (&mut self) -> Option<(usize, usize, u8)> {
-        loop {
-            if self.pos >= self.max {
-                return None;
+    pub fn is_wall(&self, x: usize, y: usize) -> bool {
+        self.walls.contains(&(x, y))
+    }
+
+    fn restore_walls(&self, world: &mut [u8]) {
+        for &(x, y) in self.walls.iter() {
+            let i = self.xy2i(x, y);
+            world[i] = self.world[i];
+        }
+    }
+
+    // Moves each cell by (dx, dy) onto a width x height board, dropping
+    // the cells and walls that fall outside.
+    fn resize(&mut self, width: usize, height: usize, dx: isize, dy: isize) {
+        let inside = |x: usize, y: usize| {
+            let (nx, ny) = ((x as isize) + dx, (y as isize) + dy);
+            if (nx < 0) || (ny < 0) || (nx as usize >= width) || (ny as usize >= height) {
+                None
+            } else {
+                Some((nx as usize, ny as usize))
             }
+        };
 
-            let pos = self.pos;
-            self.pos += 1;
+        let mut world = vec![0; width.checked_mul(height).expect("Width * height is too large.")];
+        for (x, y, _) in self.iter(Some(true)) {
+            if let Some((nx, ny)) = inside(x, y) {
+                world[(width * ny) + nx] = 1;
+            }
+        }
+        self.walls = self.walls.iter().filter_map(|&(x, y)| inside(x, y)).collect();
+        self.watched = self.watched.and_then(|(x, y)| inside(x, y));
+        self.world = Arc::new(world);
+        self.width = width;
+        self.height = height;
+        self.on_reset();
+    }
 
-            let cell = self.game.world[pos];
-            let live = cell > 0;
-            if (self.live == None) || (self.live == Some(live)) {
-                let x = pos % self.game.width();
-                let y = pos / self.game.width();
+    // Shares the world with `self` until either board is mutated. The
+    // snapshot has no callback.
+    pub fn snapshot(&self) -> LifeGame {
+        let mut game = LifeGame::new(1, 1);
+        game.world = self.world.clone();
+        game.width = self.width;
+        game.height = self.height;
+        game.generation = self.generation;
+        game.walls = self.walls.clone();
+        game.radius = self.radius;
+        game
+    }
 
-                return Some((x, y, cell));
+    pub fn stamp(&mut self, x: usize, y: usize, cells: &[(usize, usize)]) -> &Self {
+        for &(dx, dy) in cells.iter() {
+            let (cx, cy) = (x + dx, y + dy);
+            if (cx >= self.width) || (cy >= self.height) {
+                panic!("Pattern must be inside the world.");
             }
+            self.set(cx, cy, true);
         }
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::{Arc, Mutex};
+    // 'O', 'o' and '*' are live and any other character is dead. Cells past
+    // the right or bottom edge are clipped.
+    pub fn seed_text(&mut self, x: usize, y: usize, art: &str) -> &Self {
+        for (dy, line) in art.lines().enumerate() {
+            for (dx, c) in line.chars().enumerate() {
+                if let (Some(cx), Some(cy)) = (x.checked_add(dx), y.checked_add(dy)) {
+                    if (cx < self.width) && (cy < self.height) {
+                        self.set(cx, cy, (c == 'O') || (c == 'o') || (c == '*'));
+                    }
+                }
+            }
+        }
+        self
+    }
 
-    #[test]
-    fn new_1x1() {
-        let game = LifeGame::new(1, 1);
-        assert_eq!(game.width(), 1);
-        assert_eq!(game.height(), 1);
+    pub fn set_1based(&mut self, x: usize, y: usize, live: bool) -> &Self {
+        if (x == 0) || (y == 0) {
+            panic!("1-based coordinate must be not 0.");
+        }
+        self.set(x - 1, y - 1, live)
     }
 
-    #[test]
-    fn new_100x50() {
-        let game = LifeGame::new(100, 50);
-        assert_eq!(game.width(), 100);
-        assert_eq!(game.height(), 50);
+    pub fn is_empty(&self) -> bool {
+        self.world.is_empty()
     }
 
-    #[test]
-    #[should_panic(expected = "Width or height must be not 0.")]
-    fn new_width_and_height_are_0() {
-        LifeGame::new(0, 0);
+    pub fn width(&self) -> usize {
+        self.width
     }
 
-    #[test]
-    #[should_panic(expected = "Width or height must be not 0.")]
-    fn new_width_is_0() {
-        LifeGame::new(1, 0);
+    pub fn height(&self) -> usize {
+        self.height
     }
 
-    #[test]
-    #[should_panic(expected = "Width or height must be not 0.")]
-    fn new_height_is_0() {
-        LifeGame::new(0, 1);
+    pub fn toroidal_distance(&self, a: (usize, usize), b: (usize, usize)) -> (usize, usize) {
+        for &(x, y) in [a, b].iter() {
+            if (x >= self.width) || (y >= self.height) {
+                panic!("Point must be inside the world.");
+            }
+        }
+        let distance = |a: usize, b: usize, max: usize| {
+            let d = a.abs_diff(b);
+            d.min(max - d)
+        };
+        (distance(a.0, b.0, self.width), distance(a.1, b.1, self.height))
     }
 
-    #[test]
-    fn get_default_value_is_false() {
-        let game = LifeGame::new(1, 1);
-        assert_eq!(game.get(0, 0), false);
+    // Wraps `n` onto `0..max` on a torus. An empty axis (max 0) gives 0.
+    pub fn normalize_coord(n: isize, max: usize) -> usize {
+        if max == 0 {
+            return 0;
+        }
+        n.rem_euclid(max as isize) as usize
     }
 
-    #[test]
-    #[should_panic]
-    fn get_x_over_width() {
-        let game = LifeGame::new(1, 1);
-        game.get(1, 0);
+    // Neighbors are the cells within Chebyshev distance `radius`.
+    pub fn set_neighborhood_radius(&mut self, radius: usize) -> &Self {
+        if radius == 0 {
+            panic!("Radius must be not 0.");
+        }
+        if radius > MAX_NEIGHBORHOOD_RADIUS {
+            panic!("Radius must be not over MAX_NEIGHBORHOOD_RADIUS.");
+        }
+        if ((2 * radius) + 1 > self.width) || ((2 * radius) + 1 > self.height) {
+            panic!("Neighborhood must fit inside the world.");
+        }
+        self.radius = radius;
+        self
     }
 
-    #[test]
-    #[should_panic]
-    fn get_y_over_height() {
-        let game = LifeGame::new(1, 1);
-        game.get(0, 1);
+    pub fn neighborhood_radius(&self) -> usize {
+        self.radius
     }
 
-    #[test]
-    fn set_to_true_and_get() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(0, 0, true);
-        assert_eq!(game.get(0, 0), true);
+    pub fn live_neighbors(&self, x: usize, y: usize) -> u8 {
+        saturate_u8(self.neighbors_lives(x, y))
     }
 
-    #[test]
-    fn set_to_false_and_get() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(0, 0, true);
-        game.set(0, 0, false);
-        assert_eq!(game.get(0, 0), false);
+    pub fn live_neighbors_as_u16(&self, x: usize, y: usize) -> u16 {
+        self.neighbors_lives(x, y)
     }
 
-    #[test]
-    #[should_panic]
-    fn set_x_over_width() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(1, 0, true);
+    // `wrapped` counts the live neighbors reached across a board edge.
+    pub fn live_neighbors_detailed(&self, x: usize, y: usize) -> (u8, u8) {
+        let (x, y, r) = (x as isize, y as isize, self.radius as isize);
+        let mut wrapped: u16 = 0;
+        for j in (y-r)..=(y+r) {
+            for i in (x-r)..=(x+r) {
+                let ni = LifeGame::normalize_coord(i, self.width);
+                let nj = LifeGame::normalize_coord(j, self.height);
+                let crossed = ((ni as isize) != i) || ((nj as isize) != j);
+                if crossed && self.get(ni, nj) {
+                    wrapped += 1;
+                }
+            }
+        }
+        (self.live_neighbors(x as usize, y as usize), saturate_u8(wrapped))
     }
 
-    #[test]
-    #[should_panic]
-    fn set_y_over_height() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(0, 1, true);
+    fn neighbors_lives(&self, x: usize, y: usize) -> u16 {
+        LifeGame::count_neighbors(&self.world, self.width, self.height, self.radius, x, y)
     }
 
-    #[test]
-    fn reset() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(0, 0, true);
-        game.reset();
-        assert_eq!(game.get(0, 0), false);
+    fn count_neighbors(world: &[u8], width: usize, height: usize, radius: usize, x: usize, y: usize) -> u16 {
+        let x = x as isize;
+        let y = y as isize;
+        let r = radius as isize;
+
+        if radius == 1 {
+            return NEIGHBOR_OFFSETS.iter()
+                .filter(|&&(dx, dy)| {
+                    let i = LifeGame::normalize_coord(x + dx, width);
+                    let j = LifeGame::normalize_coord(y + dy, height);
+                    world[(width * j) + i] > 0
+                })
+                .count() as u16;
+        }
+
+        let mut count: u16 = 0;
+        for j in (y-r)..=(y+r) {
+            for i in (x-r)..=(x+r) {
+                let i = LifeGame::normalize_coord(i, width);
+                let j = LifeGame::normalize_coord(j, height);
+                if world[(width * j) + i] > 0 {
+                    count += 1;
+                }
+            }
+        }
+        if world[(width * (y as usize)) + (x as usize)] > 0 {
+            count -= 1;
+        }
+        count
     }
 
-    #[test]
-    fn num_cells_default_is_0() {
-        let game = LifeGame::new(1, 1);
-        assert_eq!(game.num_cells(), 0);
+    fn cell_evolution(&self, x: usize, y: usize) -> u8 {
+        if self.is_wall(x, y) {
+            return self.get_as_u8(x, y);
+        }
+        let live = self.get(x, y);
+        let count = self.neighbors_lives(x, y);
+        LifeGame::next_state(live, count)
     }
 
-    #[test]
-    fn num_cells_is_1() {
-        let mut game = LifeGame::new(1, 1);
-        game.set(0, 0, true);
-        assert_eq!(game.num_cells(), 1);
+    pub fn will_be_alive(&self, x: usize, y: usize) -> bool {
+        self.cell_evolution(x, y) > 0
     }
 
-    #[test]
-    fn num_cells_is_5000() {
-        let mut game = LifeGame::new(100, 50);
-        for y in 0..game.height() {
-            for x in 0..game.width() {
-                game.set(x, y, true);
+    pub fn explain_cell(&self, x: usize, y: usize) -> CellTransition {
+        let live = self.get(x, y);
+        let count = self.neighbors_lives(x, y);
+        let next = LifeGame::next_state(live, count) > 0;
+        let neighbors = saturate_u8(count);
+        let transition = match (live, next) {
+            _ if self.is_wall(x, y) => Transition::Wall,
+            (true, true)   => Transition::Survive,
+            (true, false)  => if neighbors < 2 { Transition::Underpopulation } else { Transition::Overpopulation },
+            (false, true)  => Transition::Birth,
+            (false, false) => Transition::StayDead
+        };
+        CellTransition { x, y, live, neighbors, transition }
+    }
+
+    // Conway's B3/S23, shared with SparseLifeGame.
+    pub(crate) fn next_state(live: bool, count: u16) -> u8 {
+        if live {
+            match count {
+                2 | 3 => 1,
+                0 | 1 => 0,
+                _     => 0
+            }
+        } else {
+            match count {
+                3 => 1,
+                _ => 0
             }
         }
-        assert_eq!(game.num_cells(), 100 * 50);
     }
 
-    #[test]
-    fn evolution_with_generation() {
-        /* .....    .....
-         * .....    ..o..
-         * .ooo. -> ..o..
-         * .....    ..o..
-         * .....    .....
-         */
-        let mut game = LifeGame::new(5, 5);
-        game.set(1, 2, true);
-        game.set(2, 2, true);
-        game.set(3, 2, true);
-        game.evolution();
+    fn next_world(world: &[u8], width: usize, height: usize, radius: usize) -> Vec<u8> {
+        if radius == 1 {
+            return LifeGame::next_world_sliding(world, width, height);
+        }
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(3, 0), false);
-        assert_eq!(game.get(4, 0), false);
+        let mut new = vec![0; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let i = (width * y) + x;
+                let count = LifeGame::count_neighbors(world, width, height, radius, x, y);
+                new[i] = LifeGame::next_state(world[i] > 0, count);
+            }
+        }
+        new
+    }
 
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), true);
-        assert_eq!(game.get(3, 1), false);
-        assert_eq!(game.get(4, 1), false);
+    // Same as counting each Moore neighborhood, but keeps a running sum of
+    // three column sums and slides it along each row.
+    fn next_world_sliding(world: &[u8], width: usize, height: usize) -> Vec<u8> {
+        let mut new = vec![0; width * height];
+        let mut columns = vec![0u16; width];
+        for y in 0..height {
+            let above = width * ((y + height - 1) % height);
+            let below = width * ((y + 1) % height);
+            let row = &world[(width * y)..(width * (y + 1))];
+            for (x, column) in columns.iter_mut().enumerate() {
+                *column = ((world[above + x] > 0) as u16)
+                    + ((row[x] > 0) as u16)
+                    + ((world[below + x] > 0) as u16);
+            }
 
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), true);
-        assert_eq!(game.get(3, 2), false);
-        assert_eq!(game.get(4, 2), false);
+            let mut sum = columns[width - 1] + columns[0] + columns[1 % width];
+            for x in 0..width {
+                let live = row[x] > 0;
+                new[(width * y) + x] = LifeGame::next_state(live, sum - (live as u16));
+                sum = sum + columns[(x + 2) % width] - columns[(x + width - 1) % width];
+            }
+        }
+        new
+    }
 
-        assert_eq!(game.get(0, 3), false);
-        assert_eq!(game.get(1, 3), false);
-        assert_eq!(game.get(2, 3), true);
-        assert_eq!(game.get(3, 3), false);
-        assert_eq!(game.get(4, 3), false);
+    pub fn evolution(&mut self) -> &Self {
+        let world = LifeGame::next_world(&self.world, self.width, self.height, self.radius);
+        self.apply_evolution(world);
+        self
+    }
 
-        assert_eq!(game.get(0, 4), false);
-        assert_eq!(game.get(1, 4), false);
-        assert_eq!(game.get(2, 4), false);
-        assert_eq!(game.get(3, 4), false);
-        assert_eq!(game.get(4, 4), false);
+    // `p` is the chance that `evolution_noisy` kills a cell that would live.
+    pub fn set_noise(&mut self, p: f64) -> &Self {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("Noise must be between 0 and 1.");
+        }
+        self.noise = p;
+        self
     }
 
-    #[test]
-    fn evolution_with_survival() {
-        /* ....      ....
-         * .oo.  ->  .oo.
+    pub fn evolution_noisy<R: Rng>(&mut self, rng: &mut R) -> &Self {
+        let mut world = LifeGame::next_world(&self.world, self.width, self.height, self.radius);
+        for cell in world.iter_mut() {
+            if (*cell > 0) && (rng.gen::<f64>() < self.noise) {
+                *cell = 0;
+            }
+        }
+        self.apply_evolution(world);
+        self
+    }
+
+    // `evolution_noisy` with the seeded generator, or the thread's one when
+    // no seed is set.
+    pub fn evolution_noisy_by_rand(&mut self) -> &Self {
+        match self.rng.take() {
+            Some(mut rng) => {
+                self.evolution_noisy(&mut rng);
+                self.rng = Some(rng);
+            }
+            None => { self.evolution_noisy(&mut rand::thread_rng()); }
+        }
+        self
+    }
+
+    // Computes each cell's next state with `f(live, neighbors)` instead of
+    // Conway's rule.
+    pub fn evolution_with<F>(&mut self, f: F) -> &Self
+        where F: Fn(bool, u8) -> bool {
+        let world = self.world.iter().zip(self.neighbor_counts())
+            .map(|(&cell, count)| f(cell > 0, count) as u8)
+            .collect();
+        self.apply_evolution(world);
+        self
+    }
+
+    // Same result as `evolution`, but keeps the neighbor counts of the last
+    // generation and only updates them around the cells that flipped.
+    pub fn evolution_incremental(&mut self) -> &Self {
+        if self.radius != 1 {
+            return self.evolution();
+        }
+
+        // Any mutation since the last call replaced or copied the shared world.
+        let mut counts = match self.count_cache.take() {
+            Some((ref world, counts)) if Arc::ptr_eq(world, &self.world) => counts,
+            _ => self.neighbor_counts()
+        };
+
+        let mut world: Vec<u8> = self.world.iter().zip(counts.iter())
+            .map(|(&cell, &count)| LifeGame::next_state(cell > 0, count as u16))
+            .collect();
+        self.restore_walls(&mut world);
+
+        for (i, (&old, &new)) in self.world.iter().zip(world.iter()).enumerate() {
+            if old == new {
+                continue;
+            }
+            let (x, y) = ((i % self.width) as isize, (i / self.width) as isize);
+            for &(dx, dy) in NEIGHBOR_OFFSETS.iter() {
+                let nx = LifeGame::normalize_coord(x + dx, self.width);
+                let ny = LifeGame::normalize_coord(y + dy, self.height);
+                let count = &mut counts[(self.width * ny) + nx];
+                *count = if new > 0 { *count + 1 } else { *count - 1 };
+            }
+        }
+
+        self.apply_evolution(world);
+        self.count_cache = Some((self.world.clone(), counts));
+        self
+    }
+
+    pub fn evolution_changed(&mut self) -> bool {
+        let mut world = LifeGame::next_world(&self.world, self.width, self.height, self.radius);
+        self.restore_walls(&mut world);
+        let changed = world != *self.world;
+        self.apply_evolution(world);
+        changed
+    }
+
+    // Grows the board on each side before evolving whenever a live cell
+    // lies within the neighborhood radius of the edge, since births reach
+    // that far. It grows by `margin`, but at least by the radius.
+    pub fn evolution_autogrow(&mut self, margin: usize) -> &Self {
+        let band = self.radius;
+        let near_edge = self.iter(Some(true)).any(|(x, y, _)| {
+            (x < band) || (y < band) || (x + band >= self.width) || (y + band >= self.height)
+        });
+        if (margin > 0) && near_edge {
+            let margin = margin.max(band);
+            let width = self.width + (2 * margin);
+            let height = self.height + (2 * margin);
+            self.resize(width, height, margin as isize, margin as isize);
+        }
+        self.evolution()
+    }
+
+    // Crops to the live bounding box plus `margin` dead cells on each side.
+    // An empty board shrinks to the smallest size its neighborhood allows.
+    pub fn shrink_to_fit(&mut self, margin: usize) -> &Self {
+        let min_size = if self.radius > 1 { (2 * self.radius) + 1 } else { 1 };
+        let (left, top, right, bottom) = self.iter(Some(true)).fold(
+            (usize::MAX, usize::MAX, 0, 0),
+            |(l, t, r, b), (x, y, _)| (l.min(x), t.min(y), r.max(x), b.max(y)));
+
+        if left == usize::MAX {
+            let size = (2 * margin).max(min_size);
+            self.resize(size, size, 0, 0);
+        } else {
+            let width = ((right - left) + 1 + (2 * margin)).max(min_size);
+            let height = ((bottom - top) + 1 + (2 * margin)).max(min_size);
+            self.resize(width, height, (margin as isize) - (left as isize), (margin as isize) - (top as isize));
+        }
+        self
+    }
+
+    pub fn pause(&mut self) -> &Self {
+        self.running = false;
+        self
+    }
+
+    pub fn resume(&mut self) -> &Self {
+        self.running = true;
+        self
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    // Evolves only while running; returns whether a generation was computed.
+    pub fn tick(&mut self) -> bool {
+        if !self.running {
+            return false;
+        }
+        let before = self.generation;
+        self.evolution();
+        self.generation != before
+    }
+
+    pub fn evolve_while<F>(&mut self, max: usize, pred: F) -> usize
+        where F: Fn(&LifeGame) -> bool {
+        let mut steps = 0;
+        while (steps < max) && pred(self) {
+            let before = self.generation;
+            self.evolution();
+            if self.generation == before {
+                break;
+            }
+            steps += 1;
+        }
+        steps
+    }
+
+    // Runs at least one generation (unless `max_gens` is 0 or the board is
+    // empty), then stops once `budget` has elapsed.
+    pub fn step_for(&mut self, max_gens: usize, budget: Duration) -> usize {
+        let start = Instant::now();
+        let mut steps = 0;
+        while steps < max_gens {
+            let before = self.generation;
+            self.evolution();
+            if self.generation == before {
+                break;
+            }
+            steps += 1;
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        steps
+    }
+
+    pub fn evolution_frames(&mut self, n: usize) -> Vec<Vec<Vec<bool>>> {
+        let mut frames = Vec::with_capacity(n);
+        for _ in 0..n {
+            self.evolution();
+            frames.push(self.grid());
+        }
+        frames
+    }
+
+    pub fn downscale(&self, factor: usize) -> Vec<Vec<u8>> {
+        if factor == 0 {
+            panic!("Factor must be not 0.");
+        }
+
+        let width = self.width.div_ceil(factor);
+        let height = self.height.div_ceil(factor);
+        let mut blocks = vec![vec![0u8; width]; height];
+        for (x, y, _) in self.iter(Some(true)) {
+            let block = &mut blocks[y / factor][x / factor];
+            // Saturates when a block has more than 255 cells.
+            *block = block.saturating_add(1);
+        }
+        blocks
+    }
+
+    // Edge tiles are divided by the cells they actually cover.
+    pub fn density_grid(&self, block: usize) -> Vec<Vec<f64>> {
+        if block == 0 {
+            panic!("Block must be not 0.");
+        }
+
+        let width = self.width.div_ceil(block);
+        let height = self.height.div_ceil(block);
+        let mut counts = vec![vec![0usize; width]; height];
+        for (x, y, _) in self.iter(Some(true)) {
+            counts[y / block][x / block] += 1;
+        }
+        counts.iter().enumerate().map(|(by, row)| {
+            let tile_h = block.min(self.height - (by * block));
+            row.iter().enumerate().map(|(bx, &count)| {
+                let tile_w = block.min(self.width - (bx * block));
+                (count as f64) / ((tile_w * tile_h) as f64)
+            }).collect()
+        }).collect()
+    }
+
+    fn grid(&self) -> Vec<Vec<bool>> {
+        self.world
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(|&cell| cell > 0).collect())
+            .collect()
+    }
+
+    // Every evolution goes through here, so an empty board never advances.
+    fn apply_evolution(&mut self, mut world: Vec<u8>) {
+        if self.is_empty() {
+            return;
+        }
+        self.restore_walls(&mut world);
+        if self.population_range.is_none() {
+            let num_cells = self.num_cells();
+            self.observe_population(num_cells);
+        }
+        self.dirty_rect = self.world.iter().zip(world.iter()).enumerate()
+            .filter(|&(_, (old, new))| old != new)
+            .map(|(i, _)| (i % self.width, i / self.width))
+            .fold(None, |rect, (x, y)| match rect {
+                None => Some((x, y, x, y)),
+                Some((x0, y0, x1, y1)) => Some((x0.min(x), y0.min(y), x1.max(x), y1.max(y)))
+            });
+        self.prev_num_cells = self.num_cells();
+        self.world = Arc::new(world);
+        debug_assert!(self.is_binary());
+        self.generation += 1;
+
+        let num_cells = self.num_cells();
+        self.observe_population(num_cells);
+
+        if let Some((x, y)) = self.watched {
+            let live = self.get(x, y);
+            self.cell_timeline.push(live);
+        }
+
+        self.on_evolution();
+    }
+
+    // Starts a new timeline for (x, y), beginning with its current state and
+    // growing by one entry per evolution.
+    pub fn watch_cell(&mut self, x: usize, y: usize) -> &Self {
+        if (x >= self.width) || (y >= self.height) {
+            panic!("Cell must be inside the world.");
+        }
+        self.watched = Some((x, y));
+        self.cell_timeline = vec![self.get(x, y)];
+        self
+    }
+
+    pub fn cell_timeline(&self) -> &[bool] {
+        &self.cell_timeline
+    }
+
+    // (left, top, right, bottom) of the cells flipped by the last evolution.
+    pub fn last_dirty_rect(&self) -> Option<(usize, usize, usize, usize)> {
+        self.dirty_rect
+    }
+
+    pub fn generations_to_stabilize(&self, max: usize) -> Option<usize> {
+        let mut seen = HashMap::new();
+        let mut world = self.world.to_vec();
+        for generation in 0..=max {
+            let mut next = LifeGame::next_world(&world, self.width, self.height, self.radius);
+            self.restore_walls(&mut next);
+            seen.insert(world, generation);
+            if let Some(&first) = seen.get(&next) {
+                return Some(first);
+            }
+            world = next;
+        }
+        None
+    }
+
+    // Returns an empty vec for boards larger than PREDECESSOR_MAX_CELLS.
+    // The empty board never evolves, so it has no predecessor.
+    pub fn predecessors(&self, limit: usize) -> Vec<LifeGame> {
+        let len = self.world.len();
+        let mut found = Vec::new();
+        if (len == 0) || (len > PREDECESSOR_MAX_CELLS) {
+            return found;
+        }
+
+        for bits in 0u32..(1 << len) {
+            if found.len() >= limit {
+                break;
+            }
+            let world: Vec<u8> = (0..len).map(|i| ((bits >> i) & 1) as u8).collect();
+            if self.walls.iter().any(|&(x, y)| world[self.xy2i(x, y)] != self.get_as_u8(x, y)) {
+                continue;
+            }
+            let mut next = LifeGame::next_world(&world, self.width, self.height, self.radius);
+            self.restore_walls(&mut next);
+            if next == *self.world {
+                let mut game = LifeGame::with_dimensions(self.width, self.height);
+                game.world = Arc::new(world);
+                game.walls = self.walls.clone();
+                game.radius = self.radius;
+                found.push(game);
+            }
+        }
+        found
+    }
+
+    pub fn has_predecessor(&self) -> Result<bool, SearchError> {
+        if self.world.len() > PREDECESSOR_MAX_CELLS {
+            return Err(SearchError::TooLarge { cells: self.world.len() });
+        }
+        Ok(!self.predecessors(1).is_empty())
+    }
+
+    pub fn neighbor_counts(&self) -> Vec<u8> {
+        let mut counts = Vec::with_capacity(self.world.len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                counts.push(self.live_neighbors(x, y));
+            }
+        }
+        counts
+    }
+
+    // Counts over 8 (neighborhood radius above 1) go to the last bucket.
+    pub fn neighbor_histogram(&self) -> [usize; 9] {
+        let mut histogram = [0; 9];
+        for count in self.neighbor_counts() {
+            histogram[(count as usize).min(8)] += 1;
+        }
+        histogram
+    }
+
+    pub fn iter_counts_in(&self, range: RangeInclusive<u8>) -> impl Iterator<Item = (usize, usize, u8)> {
+        let width = self.width;
+        self.neighbor_counts()
+            .into_iter()
+            .enumerate()
+            .filter(move |&(_, count)| range.contains(&count))
+            .map(move |(i, count)| (i % width, i / width, count))
+    }
+
+    pub fn reset(&mut self) -> &Self {
+        let len = self.width * self.height;
+        self.world = Arc::new(vec![0; len]);
+        self.generation = 0;
+        self.population_range = None;
+        self.on_reset();
+        self
+    }
+
+    pub fn reset_by_rand(&mut self) -> &Self {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let value = match self.rng {
+                    Some(ref mut rng) => rng.gen_range(0, 100),
+                    None => rand::thread_rng().gen_range(0, 100)
+                };
+                let live = if value > 50 { 1 } else { 0 };
+                self.set_u8(x, y, live);
+            }
+        }
+        self.generation = 0;
+        self.population_range = None;
+        self.on_reset();
+        self
+    }
+
+    // Makes `reset_by_rand`, `reset_border_by_rand` and
+    // `evolution_noisy_by_rand` draw from a generator seeded with `seed`, so
+    // a whole session replays from one seed.
+    pub fn set_seed(&mut self, seed: u64) -> &Self {
+        let seed: &[usize] = &[(seed & 0xffff_ffff) as usize, (seed >> 32) as usize];
+        self.rng = Some(StdRng::from_seed(seed));
+        self
+    }
+
+    // `reset_border` with the seeded generator, or the thread's one when no
+    // seed is set.
+    pub fn reset_border_by_rand(&mut self, density: f64) -> &Self {
+        match self.rng.take() {
+            Some(mut rng) => {
+                self.reset_border(density, &mut rng);
+                self.rng = Some(rng);
+            }
+            None => { self.reset_border(density, &mut rand::thread_rng()); }
+        }
+        self
+    }
+
+    // Seeds only the outermost ring, each cell live with chance `density`.
+    // The interior is cleared.
+    pub fn reset_border<R: Rng>(&mut self, density: f64, rng: &mut R) -> &Self {
+        if !(0.0..=1.0).contains(&density) {
+            panic!("Density must be between 0 and 1.");
+        }
+
+        let mut world = vec![0; self.world.len()];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let border = (x == 0) || (y == 0) || (x == self.width - 1) || (y == self.height - 1);
+                if border && (rng.gen::<f64>() < density) {
+                    world[self.xy2i(x, y)] = 1;
+                }
+            }
+        }
+        self.world = Arc::new(world);
+        self.generation = 0;
+        self.population_range = None;
+        self.on_reset();
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn set_callback<F>(mut self, callback: F) -> Self
+        where F: CallbackFn {
+        self.callback = Box::new(callback);
+        self
+    }
+
+    pub fn set_report_neighbors(&mut self, report: bool) -> &Self {
+        self.report_neighbors = report;
+        self
+    }
+
+    pub fn set_callback_mut<F>(&mut self, callback: F) -> &Self
+        where F: CallbackFn {
+        self.callback = Box::new(callback);
+        self
+    }
+
+    // `f` receives the Set events of the cells inside `rect`, given as
+    // (left, top, right, bottom) inclusive.
+    pub fn add_region_callback<F>(&mut self, rect: (usize, usize, usize, usize), f: F) -> CallbackId
+        where F: CallbackFn {
+        let id = CallbackId(self.next_callback_id);
+        self.next_callback_id += 1;
+        self.region_callbacks.push(RegionCallback { id, rect, callback: Box::new(f) });
+        id
+    }
+
+    pub fn remove_region_callback(&mut self, id: CallbackId) -> bool {
+        let len = self.region_callbacks.len();
+        self.region_callbacks.retain(|region| region.id != id);
+        self.region_callbacks.len() != len
+    }
+
+    // Runs `body` with `callback`, which may borrow locals, in place of the
+    // installed callback. Region callbacks still fire.
+    pub fn scoped_run<F, B>(&mut self, callback: F, body: B)
+        where F: FnMut(CallbackInfo), B: FnOnce(&mut ScopedRun<'_, F>) {
+        let was_scoped = mem::replace(&mut self.scoped, true);
+        let mut scoped = ScopedRun { game: self, callback, was_scoped };
+        body(&mut scoped);
+    }
+
+    // The label is reported by the Evolution callback reaching `generation`.
+    pub fn set_label(&mut self, generation: usize, label: String) -> &Self {
+        self.labels.insert(generation, label);
+        self
+    }
+
+    // Applies recorded Set events and clears the board on Reset events.
+    // Evolution events are skipped since the board evolves on its own.
+    // A Reset that left live cells (random fills, shifts, transposes...)
+    // does not record them, so the log is rejected before anything is
+    // applied, as is any event recorded on a board of another size.
+    pub fn replay(&mut self, events: &[CallbackInfo]) -> Result<(), ReplayError> {
+        for (index, info) in events.iter().enumerate() {
+            if (info.width != self.width) || (info.height != self.height) {
+                return Err(ReplayError::SizeMismatch { index, width: info.width, height: info.height });
+            }
+            if (info.event == CallbackEvent::Reset) && (info.num_cells != 0) {
+                return Err(ReplayError::UnreplayableReset { index, num_cells: info.num_cells });
+            }
+        }
+
+        for info in events.iter() {
+            match info.event {
+                CallbackEvent::Reset => { self.reset(); }
+                CallbackEvent::Set => {
+                    if let Some(ref cell) = info.cell {
+                        self.set(cell.x, cell.y, cell.live);
+                    }
+                }
+                CallbackEvent::Evolution => {}
+            }
+        }
+        Ok(())
+    }
+
+    // Widens the min/max population range to include `num_cells`.
+    fn observe_population(&mut self, num_cells: usize) {
+        self.population_range = Some(match self.population_range {
+            None => (num_cells, num_cells),
+            Some((min, max)) => (min.min(num_cells), max.max(num_cells))
+        });
+    }
+
+    // Inside `scoped_run` events wait for the scoped callback instead.
+    fn emit(&mut self, info: CallbackInfo) {
+        if self.scoped {
+            self.pending.push(info);
+        } else {
+            (self.callback)(info);
+        }
+    }
+
+    fn on_reset(&mut self) {
+        let num_cells = self.num_cells();
+        self.observe_population(num_cells);
+        self.emit(
+            CallbackInfo {
+                event: CallbackEvent::Reset,
+                generation: self.generation,
+                width: self.width,
+                height: self.height,
+                num_cells,
+                cell: None,
+                label: None
+            });
+    }
+
+    fn on_set(&mut self, x: usize, y: usize, live: u8) {
+        let live = live == 1;
+        let neighbors = if self.report_neighbors { Some(self.live_neighbors(x, y)) } else { None };
+        let num_cells = self.num_cells();
+        self.observe_population(num_cells);
+        let info = CallbackInfo {
+            event: CallbackEvent::Set,
+            generation: self.generation,
+            width: self.width,
+            height: self.height,
+            num_cells,
+            cell: Some(CellInfo { x, y, live, neighbors }),
+            label: None
+        };
+        for region in self.region_callbacks.iter_mut() {
+            let (x0, y0, x1, y1) = region.rect;
+            if (x0..=x1).contains(&x) && (y0..=y1).contains(&y) {
+                (region.callback)(info.clone());
+            }
+        }
+        self.emit(info);
+    }
+
+    fn on_evolution(&mut self) {
+        let num_cells = self.num_cells();
+        let label = self.labels.get(&self.generation).cloned();
+        self.emit(
+            CallbackInfo {
+                event: CallbackEvent::Evolution,
+                generation: self.generation,
+                width: self.width,
+                height: self.height,
+                num_cells,
+                cell: None,
+                label
+            });
+    }
+
+    pub fn population_delta(&self) -> isize {
+        if self.generation == 0 {
+            0
+        } else {
+            (self.num_cells() as isize) - (self.prev_num_cells as isize)
+        }
+    }
+
+    pub fn pattern_fingerprint(&self) -> u64 {
+        let left = self.iter(Some(true)).map(|(x, _, _)| x).min().unwrap_or(0);
+        let top = self.iter(Some(true)).map(|(_, y, _)| y).min().unwrap_or(0);
+        self.iter(Some(true)).fold(FNV_OFFSET_BASIS, |hash, (x, y, _)| {
+            let hash = fnv1a(hash, &((x - left) as u64).to_le_bytes());
+            fnv1a(hash, &((y - top) as u64).to_le_bytes())
+        })
+    }
+
+    pub fn checksum(&self) -> u64 {
+        let hash = fnv1a(FNV_OFFSET_BASIS, &(self.width as u64).to_le_bytes());
+        let hash = fnv1a(hash, &(self.height as u64).to_le_bytes());
+        fnv1a(hash, &self.world)
+    }
+
+    // Smallest and largest populations since the last reset, counting edits
+    // between generations as well as evolution.
+    pub fn population_min(&self) -> usize {
+        self.population_range.map_or(self.num_cells(), |(min, _)| min)
+    }
+
+    pub fn population_max(&self) -> usize {
+        self.population_range.map_or(self.num_cells(), |(_, max)| max)
+    }
+
+    pub fn row_populations(&self) -> Vec<usize> {
+        self.world.chunks(self.width.max(1)).map(count_live).collect()
+    }
+
+    pub fn column_populations(&self) -> Vec<usize> {
+        let mut columns = vec![0; self.width];
+        for (x, _, _) in self.iter(Some(true)) {
+            columns[x] += 1;
+        }
+        columns
+    }
+
+    // [NW, NE, SW, SE]. On odd dimensions the center column goes west and
+    // the center row goes north.
+    pub fn quadrant_populations(&self) -> [usize; 4] {
+        let (mid_x, mid_y) = (self.width.div_ceil(2), self.height.div_ceil(2));
+        let mut quadrants = [0; 4];
+        for (x, y, _) in self.iter(Some(true)) {
+            let east = (x >= mid_x) as usize;
+            let south = (y >= mid_y) as usize;
+            quadrants[(south * 2) + east] += 1;
+        }
+        quadrants
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.world.iter().all(|&cell| cell <= 1)
+    }
+
+    pub fn num_cells(&self) -> usize {
+        count_live(&self.world)
+    }
+
+    pub fn pretty_print(&self) -> String {
+        let digits = |n: usize| n.to_string().len();
+        let (col_w, row_w) = (digits(self.width.saturating_sub(1)), digits(self.height.saturating_sub(1)));
+
+        let mut s = " ".repeat(row_w);
+        for x in 0..self.width {
+            s.push_str(&format!(" {:>w$}", x, w = col_w));
+        }
+        s.push('\n');
+        for y in 0..self.height {
+            s.push_str(&format!("{:>w$}", y, w = row_w));
+            for x in 0..self.width {
+                let cell = if self.get(x, y) { "o" } else { "." };
+                s.push_str(&format!(" {:>w$}", cell, w = col_w));
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    // The dead color fills the background, with one rect per live cell.
+    pub fn to_svg(&self, cell_size: u32, live_color: &str, dead_color: &str) -> String {
+        let size = cell_size as usize;
+        let (width, height) = (self.width * size, self.height * size);
+        let (live_color, dead_color) = (escape_xml_attr(live_color), escape_xml_attr(dead_color));
+        let mut s = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+            width, height);
+        s.push_str(&format!("<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n", width, height, dead_color));
+        for (x, y, _) in self.iter(Some(true)) {
+            s.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                                x * size, y * size, size, size, live_color));
+        }
+        s.push_str("</svg>\n");
+        s
+    }
+
+    // Top-left offsets where the pattern's bounding box matches exactly,
+    // including matches that wrap around the edges.
+    pub fn find_pattern(&self, pattern: &[(usize, usize)], pattern_w: usize, pattern_h: usize) -> Vec<(usize, usize)> {
+        if pattern.iter().any(|&(px, py)| (px >= pattern_w) || (py >= pattern_h)) {
+            panic!("Pattern must be inside its bounding box.");
+        }
+        if (pattern_w == 0) || (pattern_h == 0) || (pattern_w > self.width) || (pattern_h > self.height) {
+            return Vec::new();
+        }
+        let live: HashSet<(usize, usize)> = pattern.iter().cloned().collect();
+
+        let mut found = Vec::new();
+        for oy in 0..self.height {
+            for ox in 0..self.width {
+                let matches = (0..pattern_h).all(|py| (0..pattern_w).all(|px| {
+                    let x = (ox + px) % self.width;
+                    let y = (oy + py) % self.height;
+                    self.get(x, y) == live.contains(&(px, py))
+                }));
+                if matches {
+                    found.push((ox, oy));
+                }
+            }
+        }
+        found
+    }
+
+    pub fn touched_boundary(&self) -> bool {
+        self.iter(Some(true))
+            .any(|(x, y, _)| (x == 0) || (y == 0) || (x == self.width - 1) || (y == self.height - 1))
+    }
+
+    pub fn live_set(&self) -> HashSet<(usize, usize)> {
+        self.iter(Some(true)).map(|(x, y, _)| (x, y)).collect()
+    }
+
+    // Live cells touching across a board edge belong to the same cluster.
+    pub fn count_clusters(&self, diagonal: bool) -> usize {
+        self.clusters(diagonal).len()
+    }
+
+    // Cells are in row-major order. Among clusters of the same size, the one
+    // reached first in row-major order wins.
+    pub fn largest_cluster(&self, diagonal: bool) -> Vec<(usize, usize)> {
+        let mut largest = self.clusters(diagonal).into_iter()
+            .fold(Vec::new(), |largest, cluster| if cluster.len() > largest.len() { cluster } else { largest });
+        largest.sort_by_key(|&(x, y)| (y, x));
+        largest
+    }
+
+    fn clusters(&self, diagonal: bool) -> Vec<Vec<(usize, usize)>> {
+        let offsets: &[(isize, isize)] =
+            if diagonal {
+                &NEIGHBOR_OFFSETS
+            } else {
+                &[(0, -1), (-1, 0), (1, 0), (0, 1)]
+            };
+        let mut remaining = self.live_set();
+        let mut clusters = Vec::new();
+        for (x, y, _) in self.iter(Some(true)) {
+            if !remaining.remove(&(x, y)) {
+                continue;
+            }
+            let mut cluster = Vec::new();
+            let mut stack = vec![(x, y)];
+            while let Some((cx, cy)) = stack.pop() {
+                cluster.push((cx, cy));
+                for &(dx, dy) in offsets.iter() {
+                    let nx = LifeGame::normalize_coord((cx as isize) + dx, self.width);
+                    let ny = LifeGame::normalize_coord((cy as isize) + dy, self.height);
+                    if remaining.remove(&(nx, ny)) {
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            clusters.push(cluster);
+        }
+        clusters
+    }
+
+    pub fn symmetry(&self) -> SymmetrySet {
+        let (w, h) = (self.width, self.height);
+        let invariant = |f: &dyn Fn(usize, usize) -> (usize, usize)| {
+            self.iter(Some(true)).all(|(x, y, _)| {
+                let (nx, ny) = f(x, y);
+                self.get(nx, ny)
+            })
+        };
+        SymmetrySet {
+            horizontal: invariant(&|x, y| (w - 1 - x, y)),
+            vertical: invariant(&|x, y| (x, h - 1 - y)),
+            rotation_180: invariant(&|x, y| (w - 1 - x, h - 1 - y)),
+            diagonal: (w == h) && invariant(&|x, y| (y, x))
+        }
+    }
+
+    pub fn union(&self, other: &LifeGame) -> Result<LifeGame, DimensionError> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &LifeGame) -> Result<LifeGame, DimensionError> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    fn combine<F>(&self, other: &LifeGame, f: F) -> Result<LifeGame, DimensionError>
+        where F: Fn(u8, u8) -> u8 {
+        if (self.width != other.width) || (self.height != other.height) {
+            return Err(DimensionError::SizeMismatch {
+                width: self.width,
+                height: self.height,
+                other_width: other.width,
+                other_height: other.height
+            });
+        }
+        let world = self.world.iter().zip(other.world.iter()).map(|(&a, &b)| f(a, b)).collect();
+        LifeGame::new_with_world(self.width, self.height, world)
+    }
+
+    pub fn iter(&self, live: Option<bool>) -> LifeGameIterBool<'_> {
+        LifeGameIterBool {
+            pos: 0,
+            max: self.width() * self.height(),
+            live,
+            game: self
+        }
+    }
+
+    pub fn iter_stamped(&self, live: Option<bool>) -> impl Iterator<Item = (usize, usize, bool, usize)> + '_ {
+        let generation = self.generation;
+        self.iter(live).map(move |(x, y, l)| (x, y, l, generation))
+    }
+
+    pub fn iter_col_major(&self, live: Option<bool>) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        (0..self.width)
+            .flat_map(move |x| (0..self.height).map(move |y| (x, y, self.get(x, y))))
+            .filter(move |&(_, _, l)| live.is_none() || (live == Some(l)))
+    }
+
+    // Live cells yield their live neighbor count and dead cells yield 0.
+    pub fn iter_as_u8(&self, live: Option<bool>) -> LifeGameIterU8<'_> {
+        LifeGameIterU8 {
+            pos: 0,
+            max: self.width() * self.height(),
+            live,
+            counts: self.neighbor_counts(),
+            game: self
+        }
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+pub fn evolve_all(games: &mut [LifeGame]) {
+    for game in games.iter_mut() {
+        game.evolution();
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub fn evolve_all(games: &mut [LifeGame]) {
+    // The callbacks are not `Send`, so only the next worlds are computed in parallel.
+    let worlds: Vec<Vec<u8>> = games
+        .iter()
+        .map(|game| (&game.world[..], game.width, game.height, game.radius))
+        .collect::<Vec<_>>()
+        .par_iter()
+        .map(|&(world, width, height, radius)| LifeGame::next_world(world, width, height, radius))
+        .collect();
+    for (game, world) in games.iter_mut().zip(worlds) {
+        game.apply_evolution(world);
+    }
+}
+
+impl CallbackInfo {
+    pub fn is_reset(&self) -> bool {
+        self.event == CallbackEvent::Reset
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.event == CallbackEvent::Set
+    }
+
+    pub fn is_evolution(&self) -> bool {
+        self.event == CallbackEvent::Evolution
+    }
+}
+
+impl fmt::Display for LifeGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let summary = format!("({}, {})", self.width, self.height);
+
+        let mut world = String::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                //let live = self.get(x, y);
+                //let cell = if live { "o " } else { "x " };
+                let cell = &self.get_as_u8(x, y).to_string();
+                world.push_str(cell);
+            }
+            world.push('\n');
+        }
+
+        write!(f, "{}\n{}", summary, world)
+    }
+}
+
+impl fmt::Debug for LifeGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LifeGame {{ width: {}, height: {}, generation: {}, population: {} }}",
+                 self.width, self.height, self.generation, self.num_cells())?;
+        for row in self.world.chunks(self.width.max(1)) {
+            let line: String = row.iter().map(|&cell| if cell > 0 { 'o' } else { '.' }).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DimensionError::Zero => write!(f, "width or height is 0"),
+            DimensionError::LengthMismatch { width, height, len } =>
+                write!(f, "{} cells do not fit a {}x{} world", len, width, height),
+            DimensionError::SizeMismatch { width, height, other_width, other_height } =>
+                write!(f, "{}x{} world does not match {}x{} world", width, height, other_width, other_height),
+        }
+    }
+}
+
+impl error::Error for DimensionError {}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidMagic => write!(f, "not a lifegame binary"),
+            DecodeError::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            DecodeError::Truncated => write!(f, "data is truncated"),
+            DecodeError::TrailingData => write!(f, "unexpected data after the cells"),
+            DecodeError::TooLarge => write!(f, "world is too large"),
+            DecodeError::InvalidBase64 => write!(f, "invalid base64 string"),
+            DecodeError::Dimension(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for DecodeError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InvalidOffset { line } => write!(f, "line {}: invalid #P offset", line),
+            ParseError::InvalidCell { line, cell } => write!(f, "line {}: invalid cell '{}'", line, cell),
+            ParseError::UnsupportedLine { line } => write!(f, "line {}: unsupported line", line),
+            ParseError::TooLarge => write!(f, "pattern is too large"),
+            ParseError::Empty => write!(f, "pattern has no cells"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RleError::Io(ref e) => write!(f, "{}", e),
+            RleError::MissingHeader => write!(f, "missing 'x = .., y = ..' header"),
+            RleError::InvalidHeader { line } => write!(f, "line {}: invalid header", line),
+            RleError::InvalidToken { line, token } => write!(f, "line {}: invalid token '{}'", line, token),
+            RleError::OutOfBounds { line } => write!(f, "line {}: cells outside of the header size", line),
+            RleError::UnsupportedRule { line, ref rule } => write!(f, "line {}: unsupported rule '{}'", line, rule),
+        }
+    }
+}
+
+impl error::Error for RleError {}
+
+#[cfg(feature = "image")]
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImageError::Image(ref e) => write!(f, "{}", e),
+            ImageError::Dimension(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl error::Error for ImageError {}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SearchError::TooLarge { cells } =>
+                write!(f, "{} cells are over the search limit of {}", cells, PREDECESSOR_MAX_CELLS),
+        }
+    }
+}
+
+impl error::Error for SearchError {}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::SizeMismatch { index, width, height } =>
+                write!(f, "event {} was recorded on a {}x{} world", index, width, height),
+            ReplayError::UnreplayableReset { index, num_cells } =>
+                write!(f, "event {} resets to {} live cells that are not recorded", index, num_cells),
+        }
+    }
+}
+
+impl error::Error for ReplayError {}
+
+impl<'a> Iterator for LifeGameIterBool<'a> {
+    type Item = (usize, usize, bool);
+    fn next (&mut self) -> Option<(usize, usize, bool)> {
+        loop {
+            if self.pos >= self.max {
+                return None;
+            }
+
+            let pos = self.pos;
+            self.pos += 1;
+
+            let live = self.game.world[pos] > 0;
+            if self.live.is_none() || (self.live == Some(live)) {
+                let x = pos % self.game.width();
+                let y = pos / self.game.width();
+
+                return Some((x, y, live));
+            }
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<(usize, usize, bool)> {
+        if self.live.is_none() {
+            self.pos = self.pos.saturating_add(n).min(self.max);
+        } else {
+            for _ in 0..n {
+                self.next()?;
+            }
+        }
+        self.next()
+    }
+
+    fn last(mut self) -> Option<(usize, usize, bool)> {
+        if self.live.is_none() {
+            if self.pos < self.max {
+                self.pos = self.max - 1;
+            }
+            self.next()
+        } else {
+            self.fold(None, |_, cell| Some(cell))
+        }
+    }
+
+    fn count(self) -> usize {
+        match self.live {
+            None => self.max.saturating_sub(self.pos),
+            Some(true) if self.pos == 0 => self.game.num_cells(),
+            Some(false) if self.pos == 0 => self.max - self.game.num_cells(),
+            Some(_) => self.fold(0, |count, _| count + 1)
+        }
+    }
+}
+
+impl<'a> Iterator for LifeGameIterU8<'a> {
+    type Item = (usize, usize, u8);
+    fn next (&mut self) -> Option<(usize, usize, u8)> {
+        loop {
+            if self.pos >= self.max {
+                return None;
+            }
+
+            let pos = self.pos;
+            self.pos += 1;
+
+            let live = self.game.world[pos] > 0;
+            if self.live.is_none() || (self.live == Some(live)) {
+                let x = pos % self.game.width();
+                let y = pos / self.game.width();
+                let cell = if live { self.counts[pos] } else { 0 };
+
+                return Some((x, y, cell));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn new_1x1() {
+        let game = LifeGame::new(1, 1);
+        assert_eq!(game.width(), 1);
+        assert_eq!(game.height(), 1);
+    }
+
+    #[test]
+    fn new_100x50() {
+        let game = LifeGame::new(100, 50);
+        assert_eq!(game.width(), 100);
+        assert_eq!(game.height(), 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "Width or height must be not 0.")]
+    fn new_width_and_height_are_0() {
+        LifeGame::new(0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Width or height must be not 0.")]
+    fn new_width_is_0() {
+        LifeGame::new(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Width or height must be not 0.")]
+    fn new_height_is_0() {
+        LifeGame::new(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Width or height must be not over MAX_DIMENSION.")]
+    fn new_width_is_over_max_dimension() {
+        LifeGame::new(MAX_DIMENSION + 1, 1);
+    }
+
+    #[test]
+    fn empty() {
+        let mut game = LifeGame::empty();
+        assert_eq!(game.is_empty(), true);
+        assert_eq!(game.width(), 0);
+        assert_eq!(game.height(), 0);
+        assert_eq!(game.num_cells(), 0);
+        assert_eq!(game.get(0, 0), false);
+        game.set(0, 0, true);
+        assert_eq!(game.num_cells(), 0);
+        game.evolution();
+        assert_eq!(game.evolution_changed(), false);
+        game.evolution_incremental();
+        game.evolution_with(|live, _| !live);
+        game.set_noise(0.5);
+        game.evolution_noisy(&mut rand::thread_rng());
+        game.evolution_autogrow(2);
+        assert_eq!(game.tick(), false);
+        assert_eq!(game.step_for(3, Duration::from_secs(60)), 0);
+        assert_eq!(game.evolve_while(3, |_| true), 0);
+        evolve_all(std::slice::from_mut(&mut game));
+        assert_eq!(game.generation(), 0);
+        assert_eq!(game.is_empty(), true);
+        assert_eq!(format!("{:?}", game), "LifeGame { width: 0, height: 0, generation: 0, population: 0 }\n");
+    }
+
+    #[test]
+    fn new_for_cells() {
+        let game = LifeGame::new_for_cells(10000, 2.0);
+        let cells = game.width() * game.height();
+        let ratio = (game.width() as f64) / (game.height() as f64);
+        assert!((9800..=10200).contains(&cells));
+        assert!((ratio - 2.0).abs() < 0.05);
+
+        let game = LifeGame::new_for_cells(0, 0.001);
+        assert_eq!((game.width(), game.height()), (1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Aspect must be a positive number.")]
+    fn new_for_cells_aspect_is_0() {
+        LifeGame::new_for_cells(100, 0.0);
+    }
+
+    #[test]
+    fn neighbors_lives_at_far_corner() {
+        let mut game = LifeGame::new(1000, 1000);
+        game.set(998, 998, true);
+        game.set(0, 999, true);
+        game.set(999, 0, true);
+        game.set(0, 0, true);
+        assert_eq!(game.neighbors_lives(999, 999), 4);
+        assert_eq!(game.neighbors_lives(998, 998), 0);
+    }
+
+    #[test]
+    fn normalize_coord() {
+        assert_eq!(LifeGame::normalize_coord(0, 3), 0);
+        assert_eq!(LifeGame::normalize_coord(-1, 3), 2);
+        assert_eq!(LifeGame::normalize_coord(3, 3), 0);
+        assert_eq!(LifeGame::normalize_coord(-3 - 1, 3), 2);
+        assert_eq!(LifeGame::normalize_coord(2 * 3, 3), 0);
+        assert_eq!(LifeGame::normalize_coord((2 * 3) + 1, 3), 1);
+        assert_eq!(LifeGame::normalize_coord(0, 0), 0);
+        assert_eq!(LifeGame::normalize_coord(isize::MIN, 0), 0);
+    }
+
+    #[test]
+    fn neighborhood_radius_2() {
+        let mut game = LifeGame::new(7, 7);
+        for y in 1..6 {
+            for x in 1..6 {
+                game.set(x, y, true);
+            }
+        }
+        assert_eq!(game.neighborhood_radius(), 1);
+        assert_eq!(game.live_neighbors(3, 3), 8);
+
+        game.set_neighborhood_radius(2);
+        assert_eq!(game.live_neighbors(3, 3), 24);
+        assert_eq!(game.live_neighbors_detailed(0, 0), (9, 5));
+        assert_eq!(game.live_neighbors_detailed(6, 6), (9, 5));
+
+        game.evolution();
+        assert_eq!(game.get(3, 3), false);
+        assert_eq!(game.get(0, 0), false);
+    }
+
+    #[test]
+    fn neighborhood_radius_9() {
+        let mut game = LifeGame::new(20, 20);
+        for y in 0..20 {
+            for x in 0..20 {
+                game.set(x, y, true);
+            }
+        }
+        game.set_neighborhood_radius(9);
+        assert_eq!(game.live_neighbors_as_u16(10, 10), (19 * 19) - 1);
+        assert_eq!(game.live_neighbors(10, 10), 255);
+        assert_eq!(game.explain_cell(10, 10).transition, Transition::Overpopulation);
+
+        game.evolution();
+        assert_eq!(game.num_cells(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Radius must be not 0.")]
+    fn neighborhood_radius_0() {
+        let mut game = LifeGame::new(5, 5);
+        game.set_neighborhood_radius(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Neighborhood must fit inside the world.")]
+    fn neighborhood_radius_over_world() {
+        let mut game = LifeGame::new(5, 4);
+        game.set_neighborhood_radius(2);
+    }
+
+    #[test]
+    fn live_neighbors_detailed_at_corner() {
+        /* oo.o
+         * ...o
+         * ....
+         * o..o
+         */
+        let mut game = LifeGame::new(4, 4);
+        for &(x, y) in [(0, 0), (1, 0), (3, 0), (3, 1), (0, 3), (3, 3)].iter() {
+            game.set(x, y, true);
+        }
+        assert_eq!(game.live_neighbors_detailed(0, 0), (5, 4));
+        assert_eq!(game.live_neighbors_detailed(1, 1), (2, 0));
+    }
+
+    #[test]
+    fn new_with_world() {
+        let game = LifeGame::new_with_world(3, 2, vec![0, 1, 0, 1, 0, 2]).unwrap();
+        assert_eq!(game.width(), 3);
+        assert_eq!(game.height(), 2);
+        assert_eq!(game.generation(), 0);
+        assert_eq!(game.get(1, 0), true);
+        assert_eq!(game.get(0, 1), true);
+        assert_eq!(game.get(2, 1), true);
+        assert_eq!(game.num_cells(), 3);
+        assert_eq!(game.is_binary(), true);
+    }
+
+    #[test]
+    fn new_with_world_errors() {
+        assert_eq!(LifeGame::new_with_world(3, 2, vec![0; 5]).err(),
+                   Some(DimensionError::LengthMismatch { width: 3, height: 2, len: 5 }));
+        assert_eq!(LifeGame::new_with_world(0, 2, vec![]).err(), Some(DimensionError::Zero));
+    }
+
+    #[test]
+    fn from_bool_slice() {
+        /* Blinker
+         * .....
+         * ..o..
+         * ..o..
+         * ..o..
+         * .....
+         */
+        let mut data = [false; 25];
+        data[7] = true;
+        data[12] = true;
+        data[17] = true;
+        let mut game = LifeGame::from_bool_slice(5, 5, &data).unwrap();
+        assert_eq!(game.get(2, 1), true);
+        assert_eq!(game.get(2, 2), true);
+        assert_eq!(game.get(2, 3), true);
+        assert_eq!(game.num_cells(), 3);
+
+        game.evolution();
+        assert_eq!(game.get(1, 2), true);
+        assert_eq!(game.get(2, 2), true);
+        assert_eq!(game.get(3, 2), true);
+        assert_eq!(game.num_cells(), 3);
+
+        assert_eq!(LifeGame::from_bool_slice(5, 5, &data[..24]).err(),
+                   Some(DimensionError::LengthMismatch { width: 5, height: 5, len: 24 }));
+    }
+
+    #[test]
+    fn to_bytes_and_from_bytes() {
+        let mut game = LifeGame::new(5, 3);
+        game.set(0, 0, true);
+        game.set(4, 0, true);
+        game.set(2, 1, true);
+        game.set(4, 2, true);
+        game.evolution();
+        game.set(3, 2, true);
+
+        let bytes = game.to_bytes();
+        assert_eq!(bytes.len(), 29 + 2);
+
+        let decoded = LifeGame::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.width(), 5);
+        assert_eq!(decoded.height(), 3);
+        assert_eq!(decoded.generation(), 1);
+        assert_eq!(decoded.iter(None).collect::<Vec<_>>(), game.iter(None).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn from_bytes_errors() {
+        let bytes = LifeGame::new(5, 3).to_bytes();
+        assert_eq!(LifeGame::from_bytes(&bytes[..30]).err(), Some(DecodeError::Truncated));
+        assert_eq!(LifeGame::from_bytes(&bytes[..10]).err(), Some(DecodeError::Truncated));
+        assert_eq!(LifeGame::from_bytes(b"garbage").err(), Some(DecodeError::InvalidMagic));
+
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert_eq!(LifeGame::from_bytes(&extra).err(), Some(DecodeError::TrailingData));
+
+        let mut version = bytes.clone();
+        version[4] = 9;
+        assert_eq!(LifeGame::from_bytes(&version).err(), Some(DecodeError::UnsupportedVersion(9)));
+
+        let mut large = bytes.clone();
+        large[12] = 0xff;
+        assert_eq!(LifeGame::from_bytes(&large).err(), Some(DecodeError::TooLarge));
+
+        let mut zero = bytes.clone();
+        zero[5] = 0;
+        zero.truncate(29);
+        assert_eq!(LifeGame::from_bytes(&zero).err(), Some(DecodeError::Dimension(DimensionError::Zero)));
+    }
+
+    #[test]
+    fn to_base64_and_from_base64() {
+        let mut game = LifeGame::new(7, 5);
+        game.set(0, 0, true);
+        game.set(3, 2, true);
+        game.set(6, 4, true);
+        game.evolution();
+        game.set(2, 1, true);
+
+        let s = game.to_base64();
+        assert!(s.bytes().all(|c| c.is_ascii_alphanumeric() || (c == b'-') || (c == b'_')));
+
+        let decoded = LifeGame::from_base64(&s).unwrap();
+        assert_eq!(decoded.width(), 7);
+        assert_eq!(decoded.height(), 5);
+        assert_eq!(decoded.generation(), 1);
+        assert_eq!(decoded.to_bytes(), game.to_bytes());
+    }
+
+    #[test]
+    fn from_base64_errors() {
+        let s = LifeGame::new(3, 3).to_base64();
+        assert_eq!(LifeGame::from_base64("TElG+0").err(), Some(DecodeError::InvalidBase64));
+        assert_eq!(LifeGame::from_base64(&s[..(s.len() - 1)]).err(), Some(DecodeError::InvalidBase64));
+        assert_eq!(LifeGame::from_base64(&s[..(s.len() - 4)]).err(), Some(DecodeError::Truncated));
+        assert_eq!(LifeGame::from_base64("").err(), Some(DecodeError::InvalidMagic));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn from_image() {
+        /* Black pixels on white
+         * .o.
+         * ..o
+         * ooo
+         * ...
+         */
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let png = image::GrayImage::from_fn(3, 4, |x, y| {
+            let live = glider.contains(&(x as usize, y as usize));
+            image::Luma([if live { 0x10 } else { 0xf0 }])
+        });
+        let path = std::env::temp_dir().join(format!("lifegame-from-image-{}.png", std::process::id()));
+        png.save(&path).unwrap();
+
+        let game = LifeGame::from_image(&path, 0x80);
+        std::fs::remove_file(&path).unwrap();
+        let game = game.unwrap();
+        assert_eq!((game.width(), game.height()), (3, 4));
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, glider.to_vec());
+
+        match LifeGame::from_image(&path, 0x80) {
+            Err(ImageError::Image(_)) => (),
+            _ => panic!("missing file must be an image error"),
+        }
+    }
+
+    #[test]
+    fn from_life105() {
+        let game = LifeGame::from_life105(
+            "#Life 1.05\n\
+             #D Two blocks\n\
+             #N\n\
+             #P -2 -1\n\
+             .*\n\
+             **\n\
+             #P 1 2\n\
+             ***\n").unwrap();
+
+        assert_eq!(game.width(), 6);
+        assert_eq!(game.height(), 4);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(1, 0), (0, 1), (1, 1), (3, 3), (4, 3), (5, 3)]);
+    }
+
+    #[test]
+    fn from_life105_errors() {
+        assert_eq!(LifeGame::from_life105("#P 0\n*\n").err(), Some(ParseError::InvalidOffset { line: 1 }));
+        assert_eq!(LifeGame::from_life105("#P 0 0\n*o\n").err(), Some(ParseError::InvalidCell { line: 2, cell: 'o' }));
+        assert_eq!(LifeGame::from_life105("#R 34/34\n*\n").err(), Some(ParseError::UnsupportedLine { line: 1 }));
+        assert_eq!(LifeGame::from_life105("#D nothing\n").err(), Some(ParseError::Empty));
+        assert_eq!(LifeGame::from_life105("#P 9223372036854775807 0\n**\n").err(), Some(ParseError::InvalidOffset { line: 1 }));
+        assert_eq!(LifeGame::from_life105("#P 0 9223372036854775807\n*\n*\n").err(), Some(ParseError::InvalidOffset { line: 1 }));
+        assert_eq!(LifeGame::from_life105("#P -9223372036854775808 0\n*\n#P 9223372036854775806 0\n*\n").err(), Some(ParseError::TooLarge));
+    }
+
+    #[test]
+    fn from_rle_reader() {
+        let rle = "#N Glider\n\
+                   x = 3, y = 3, rule = B3/S23\n\
+                   bob$2bo$\n\
+                   3o!\n";
+        let game = LifeGame::from_rle_reader(io::Cursor::new(rle)).unwrap();
+
+        assert_eq!(game.width(), 3);
+        assert_eq!(game.height(), 3);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn to_rle() {
+        let mut game = LifeGame::new(3, 3);
+        game.stamp(0, 0, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(game.to_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+        let mut game = LifeGame::new(5, 6);
+        game.set(2, 3, true);
+        game.set(0, 5, true);
+        assert_eq!(game.to_rle(), "x = 5, y = 6, rule = B3/S23\n3$2bo2$o!\n");
+
+        assert_eq!(LifeGame::new(4, 2).to_rle(), "x = 4, y = 2, rule = B3/S23\n!\n");
+    }
+
+    #[test]
+    fn to_rle_with_meta() {
+        let mut game = LifeGame::new(40, 12);
+        game.stamp(2, 1, &::patterns::gosper_glider_gun());
+        let rle = game.to_rle_with_meta("Gosper glider gun", "The first known gun.\nBill Gosper, 1970");
+
+        let lines: Vec<&str> = rle.lines().collect();
+        assert_eq!(lines[0], "#N Gosper glider gun");
+        assert_eq!(lines[1], "#C The first known gun.");
+        assert_eq!(lines[2], "#C Bill Gosper, 1970");
+        assert_eq!(lines[3], "x = 40, y = 12, rule = B3/S23");
+        assert!(lines.iter().all(|line| line.len() <= 70));
+
+        let decoded = LifeGame::from_rle(&rle).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (40, 12));
+        assert_eq!(decoded.world, game.world);
+    }
+
+    #[test]
+    fn to_rle_with_meta_hostile_text() {
+        let mut game = LifeGame::new(3, 3);
+        game.stamp(0, 0, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        let rle = game.to_rle_with_meta("evil\nx = 1, y = 1\r\n!", "one\n\nx = 9, y = 9\r\nb!");
+
+        let lines: Vec<&str> = rle.lines().collect();
+        assert_eq!(lines[0], "#N evil x = 1, y = 1  !");
+        assert_eq!(lines[1..4], ["#C one", "#C ", "#C x = 9, y = 9"]);
+        assert_eq!(lines[4], "#C b!");
+
+        let decoded = LifeGame::from_rle(&rle).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (3, 3));
+        assert_eq!(decoded.world, game.world);
+    }
+
+    #[test]
+    fn from_rle_errors() {
+        match LifeGame::from_rle("#C only a comment\n") {
+            Err(RleError::MissingHeader) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+        match LifeGame::from_rle("x = 0, y = 3\n") {
+            Err(RleError::InvalidHeader { line: 1 }) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+        match LifeGame::from_rle("x = 18446744073709551615, y = 1\n") {
+            Err(RleError::InvalidHeader { line: 1 }) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+        match LifeGame::from_rle("#N huge\nx = 4294967296, y = 4294967296\n") {
+            Err(RleError::InvalidHeader { line: 2 }) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+        match LifeGame::from_rle("x = 2, y = 2\nbo$3o!\n") {
+            Err(RleError::OutOfBounds { line: 2 }) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+        match LifeGame::from_rle("x = 2, y = 2\nbz!\n") {
+            Err(RleError::InvalidToken { line: 2, token: 'z' }) => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn from_rle_rule() {
+        for &rule in ["B3/S23", "b3/s23", "23/3"].iter() {
+            let rle = format!("x = 3, y = 1, rule = {}\n3o!\n", rule);
+            assert_eq!(LifeGame::from_rle(&rle).unwrap().num_cells(), 3);
+        }
+        assert_eq!(LifeGame::from_rle("x = 3, y = 1\n3o!\n").unwrap().num_cells(), 3);
+
+        let highlife = "#N Replicator\n\
+                        x = 5, y = 5, rule = B36/S23\n\
+                        2b3o$bo2bo$o3bo$o2bo$3o!\n";
+        match LifeGame::from_rle(highlife) {
+            Err(RleError::UnsupportedRule { line: 2, ref rule }) if rule == "B36/S23" => {}
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn get_default_value_is_false() {
+        let game = LifeGame::new(1, 1);
+        assert_eq!(game.get(0, 0), false);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_x_over_width() {
+        let game = LifeGame::new(1, 1);
+        game.get(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_y_over_height() {
+        let game = LifeGame::new(1, 1);
+        game.get(0, 1);
+    }
+
+    #[test]
+    fn set_to_true_and_get() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 0, true);
+        assert_eq!(game.get(0, 0), true);
+    }
+
+    #[test]
+    fn set_to_false_and_get() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 0, true);
+        game.set(0, 0, false);
+        assert_eq!(game.get(0, 0), false);
+    }
+
+    #[test]
+    fn debug_set_raw() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        game.debug_set_raw(1, 1, 5);
+        assert_eq!(game.get(1, 1), true);
+        assert_eq!(game.is_binary(), false);
+        assert_eq!(game.num_cells(), 2);
+        assert_eq!(game.live_neighbors(0, 0), 1);
+    }
+
+    #[test]
+    fn stamp() {
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(2, 1, &[(1, 0), (0, 1), (1, 1)]);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(3, 1), (2, 2), (3, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pattern must be inside the world.")]
+    fn stamp_over_width() {
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(4, 0, &[(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn seed_text() {
+        let mut game = LifeGame::new(10, 10);
+        game.seed_text(4, 4, ".O.\n..O\nOOO");
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(5, 4), (6, 5), (4, 6), (5, 6), (6, 6)]);
+
+        game.seed_text(4, 4, "...");
+        assert_eq!(game.get(5, 4), false);
+        assert_eq!(game.num_cells(), 4);
+    }
+
+    #[test]
+    fn seed_text_clips_at_edges() {
+        let mut game = LifeGame::new(4, 3);
+        game.seed_text(2, 1, "OOOO\nO..O\nOOOO");
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 1), (3, 1), (2, 2)]);
+
+        let mut game = LifeGame::new(4, 3);
+        game.seed_text(usize::MAX, usize::MAX - 1, "OO\nOO\nOO");
+        assert_eq!(game.num_cells(), 0);
+    }
+
+    #[test]
+    fn set_1based() {
+        let mut game = LifeGame::new(2, 2);
+        game.set_1based(1, 1, true);
+        game.set_1based(2, 2, true);
+        assert_eq!(game.get(0, 0), true);
+        assert_eq!(game.get(1, 1), true);
+        assert_eq!(game.num_cells(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "1-based coordinate must be not 0.")]
+    fn set_1based_0() {
+        let mut game = LifeGame::new(2, 2);
+        game.set_1based(0, 1, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_x_over_width() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(1, 0, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_y_over_height() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 1, true);
+    }
+
+    #[test]
+    fn clear_region() {
+        /* oooo      oooo
+         * oooo  ->  o..o
+         * oooo      o..o
+         */
+        let mut game = LifeGame::new(4, 3);
+        for y in 0..game.height() {
+            for x in 0..game.width() {
+                game.set(x, y, true);
+            }
+        }
+        game.clear_region(1, 1, 2, 2);
+
+        for (x, y, live) in game.iter(None) {
+            let hole = (x == 1 || x == 2) && (y == 1 || y == 2);
+            assert_eq!(live, !hole);
+        }
+        assert_eq!(game.num_cells(), 8);
+    }
+
+    #[test]
+    fn clear_region_fires_set_per_changed_cell() {
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |i| {
+                            if i.event == CallbackEvent::Set {
+                                *countcb.lock().unwrap() += 1;
+                            }
+                        });
+        game.set(0, 0, true);
+        game.set(1, 1, true);
+        game.clear_region(0, 0, 2, 2);
+        assert_eq!(*count.lock().unwrap(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Region must be inside the world.")]
+    fn clear_region_reversed() {
+        let mut game = LifeGame::new(3, 3);
+        game.clear_region(2, 0, 1, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Region must be inside the world.")]
+    fn clear_region_over_width() {
+        let mut game = LifeGame::new(3, 3);
+        game.clear_region(0, 0, 3, 2);
+    }
+
+    #[test]
+    fn for_each_mut() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let eventscb = events.clone();
+
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |i| {
+                            eventscb.lock().unwrap().push(i.event);
+                        });
+        game.for_each_mut(|_, _, live| *live = true);
+        game.for_each_mut(|x, _, live| {
+            if x == 1 {
+                *live = false;
+            }
+        });
+
+        for (x, _, live) in game.iter(None) {
+            assert_eq!(live, x != 1);
+        }
+        assert_eq!(*events.lock().unwrap(), vec![CallbackEvent::Reset, CallbackEvent::Reset]);
+    }
+
+    #[test]
+    fn shift_wrap() {
+        let mut game = LifeGame::new(4, 3);
+        game.set(3, 0, true);
+        game.set(1, 1, true);
+        let lost = game.shift(1, -1, true);
+
+        assert_eq!(lost, vec![]);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 0), (0, 2)]);
+    }
+
+    #[test]
+    fn shift_wrap_over_several_widths() {
+        let mut game = LifeGame::new(4, 3);
+        game.set(3, 0, true);
+        game.set(1, 1, true);
+        game.shift(1 + (4 * 3), -1 - (3 * 2), true);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 0), (0, 2)]);
+
+        game.shift(-4 * 5, 3 * 4, true);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 0), (0, 2)]);
+    }
+
+    #[test]
+    fn normalize_coord_over_several_widths() {
+        assert_eq!(LifeGame::normalize_coord(-5, 3), 1);
+        assert_eq!(LifeGame::normalize_coord(-9, 3), 0);
+        assert_eq!(LifeGame::normalize_coord(-10, 3), 2);
+        assert_eq!(LifeGame::normalize_coord(10, 3), 1);
+        assert_eq!(LifeGame::normalize_coord(302, 3), 2);
+    }
+
+    #[test]
+    fn shift_no_wrap_reports_lost_cells() {
+        let mut game = LifeGame::new(4, 3);
+        game.set(3, 0, true);
+        game.set(1, 1, true);
+        let lost = game.shift(1, 0, false);
+
+        assert_eq!(lost, vec![(3, 0)]);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 1)]);
+    }
+
+    #[test]
+    fn transpose() {
+        /* o.
+         * o.  ->  oo.
+         * .o      ..o
+         */
+        let mut game = LifeGame::new(2, 3);
+        game.set(0, 0, true);
+        game.set(0, 1, true);
+        game.set(1, 2, true);
+        game.transpose();
+
+        assert_eq!(game.width(), 3);
+        assert_eq!(game.height(), 2);
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(0, 0), (1, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn snapshot_shares_world_until_mutated() {
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+
+        let snapshot = game.snapshot();
+        assert_eq!(Arc::strong_count(&game.world), 2);
+        assert!(Arc::ptr_eq(&game.world, &snapshot.world));
+        assert_eq!(snapshot.get(2, 2), true);
+        assert_eq!(snapshot.generation(), 0);
+
+        game.set(0, 0, true);
+        assert_eq!(Arc::strong_count(&game.world), 1);
+        assert_eq!(Arc::strong_count(&snapshot.world), 1);
+        assert_eq!(snapshot.get(0, 0), false);
+
+        let snapshot = game.snapshot();
+        game.evolution();
+        assert_eq!(Arc::strong_count(&snapshot.world), 1);
+        assert_eq!(snapshot.num_cells(), 4);
+        assert_eq!(snapshot.get(1, 2), true);
+        assert_eq!(game.get(1, 2), false);
+    }
+
+    #[test]
+    fn dilate_and_erode() {
+        let mut game = LifeGame::new(7, 7);
+        game.set(3, 3, true);
+        game.dilate();
+        assert_eq!(game.num_cells(), 9);
+        for y in 2..=4 {
+            for x in 2..=4 {
+                assert_eq!(game.get(x, y), true);
+            }
+        }
+        game.erode();
+        assert_eq!(game.num_cells(), 1);
+        assert_eq!(game.get(3, 3), true);
+    }
+
+    #[test]
+    fn transpose_moves_walls() {
+        let mut game = LifeGame::new(2, 3);
+        game.set_wall(1, 2, true);
+        game.transpose();
+        assert_eq!(game.is_wall(2, 1), true);
+        assert_eq!(game.is_wall(1, 2), false);
+    }
+
+    #[test]
+    fn wall_survives_overpopulation() {
+        /* ooo
+         * oWo
+         * ooo
+         */
+        let mut game = LifeGame::new(5, 5);
+        for y in 1..4 {
+            for x in 1..4 {
+                game.set(x, y, true);
+            }
+        }
+        game.set_wall(2, 2, true);
+        assert_eq!(game.will_be_alive(2, 2), true);
+
+        game.evolution();
+        assert_eq!(game.get(2, 2), true);
+        assert_eq!(game.is_wall(2, 2), true);
+
+        game.set_wall(2, 2, false);
+        assert_eq!(game.will_be_alive(2, 2), false);
+    }
+
+    #[test]
+    fn dead_wall_is_not_born() {
+        /* .o.
+         * oW.
+         * .o.
+         */
+        let mut game = LifeGame::new(5, 5);
+        game.set(2, 1, true);
+        game.set(1, 2, true);
+        game.set(2, 3, true);
+        game.set_wall(2, 2, true);
+        assert_eq!(game.evolution_changed(), true);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Wall must be inside the world.")]
+    fn set_wall_over_width() {
+        let mut game = LifeGame::new(5, 5);
+        game.set_wall(5, 0, true);
+    }
+
+    #[test]
+    fn debug() {
+        let mut game = LifeGame::new(4, 2);
+        game.set(1, 0, true);
+        game.set(3, 1, true);
+        assert_eq!(format!("{:?}", game),
+                   "LifeGame { width: 4, height: 2, generation: 0, population: 2 }\n.o..\n...o\n");
+
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+        game.evolution();
+        let debug = format!("{:?}", game);
+        assert!(debug.contains("generation: 1"));
+        assert!(debug.contains("population: 3"));
+    }
+
+    #[test]
+    fn reset() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 0, true);
+        game.reset();
+        assert_eq!(game.get(0, 0), false);
+    }
+
+    #[test]
+    fn num_cells_default_is_0() {
+        let game = LifeGame::new(1, 1);
+        assert_eq!(game.num_cells(), 0);
+    }
+
+    #[test]
+    fn num_cells_is_1() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 0, true);
+        assert_eq!(game.num_cells(), 1);
+    }
+
+    #[test]
+    fn num_cells_counts_contaminated_cells_once() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(0, 1, true);
+        game.debug_set_raw(1, 0, 2);
+        game.debug_set_raw(0, 1, 2);
+        assert_eq!(game.num_cells(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn count_live_matches_scalar() {
+        let mut game = LifeGame::new(123, 45);
+        for _ in 0..5 {
+            game.reset_by_rand();
+            assert_eq!(count_live(&game.world), count_live_scalar(&game.world));
+        }
+        let mixed: Vec<u8> = (0..1000).map(|i| (i % 7) as u8 * 40).collect();
+        assert_eq!(count_live(&mixed), count_live_scalar(&mixed));
+    }
+
+    #[test]
+    fn num_cells_is_5000() {
+        let mut game = LifeGame::new(100, 50);
+        for y in 0..game.height() {
+            for x in 0..game.width() {
+                game.set(x, y, true);
+            }
+        }
+        assert_eq!(game.num_cells(), 100 * 50);
+    }
+
+    #[test]
+    fn is_binary_default_is_true() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        assert_eq!(game.is_binary(), true);
+    }
+
+    #[test]
+    fn is_binary_is_false_after_contamination() {
+        /* oo.      22.
+         * o..  ->  2..
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(0, 1, true);
+        game.debug_set_raw(0, 0, 2);
+        game.debug_set_raw(1, 0, 2);
+        game.debug_set_raw(0, 1, 2);
+        assert_eq!(game.is_binary(), false);
+    }
+
+    #[test]
+    fn toroidal_distance() {
+        let game = LifeGame::new(10, 8);
+        assert_eq!(game.toroidal_distance((0, 0), (9, 7)), (1, 1));
+        assert_eq!(game.toroidal_distance((9, 7), (0, 0)), (1, 1));
+        assert_eq!(game.toroidal_distance((2, 1), (4, 4)), (2, 3));
+        assert_eq!(game.toroidal_distance((0, 0), (5, 4)), (5, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Point must be inside the world.")]
+    fn toroidal_distance_over_width() {
+        let game = LifeGame::new(10, 8);
+        game.toroidal_distance((0, 0), (25, 0));
+    }
+
+    #[test]
+    fn population_delta_default_is_0() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        assert_eq!(game.population_delta(), 0);
+    }
+
+    #[test]
+    fn population_delta_grows() {
+        /* .....    .....
+         * .oo..    .oo..
+         * .o...    .oo..
+         * .....    .....
+         */
+        let mut game = LifeGame::new(5, 4);
+        game.set(1, 1, true);
+        game.set(2, 1, true);
+        game.set(1, 2, true);
+        game.evolution();
+        assert_eq!(game.population_delta(), 1);
+
+        game.evolution();
+        assert_eq!(game.population_delta(), 0);
+
+        game.reset();
+        assert_eq!(game.population_delta(), 0);
+    }
+
+    #[test]
+    fn pattern_fingerprint() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut small = LifeGame::new(10, 10);
+        let mut large = LifeGame::new(20, 20);
+        for &(x, y) in glider.iter() {
+            small.set(x + 2, y + 3, true);
+            large.set(x + 11, y + 7, true);
+        }
+        assert_eq!(small.pattern_fingerprint(), large.pattern_fingerprint());
+
+        small.evolution();
+        assert_ne!(small.pattern_fingerprint(), large.pattern_fingerprint());
+    }
+
+    #[test]
+    fn row_populations() {
+        /* o.o.
+         * ....
+         * ooo.
+         */
+        let mut game = LifeGame::new(4, 3);
+        game.stamp(0, 0, &[(0, 0), (2, 0), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(game.row_populations(), vec![2, 0, 3]);
+    }
+
+    #[test]
+    fn column_populations() {
+        /* o.o.
+         * ....
+         * ooo.
+         */
+        let mut game = LifeGame::new(4, 3);
+        game.stamp(0, 0, &[(0, 0), (2, 0), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(game.column_populations(), vec![2, 1, 2, 0]);
+    }
+
+    #[test]
+    fn quadrant_populations() {
+        /* o...o
+         * ..o..
+         * .o.oo
+         * .....
+         * o...o
+         */
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(0, 0, &[(0, 0), (4, 0), (2, 1), (1, 2), (3, 2), (4, 2), (0, 4), (4, 4)]);
+        assert_eq!(game.quadrant_populations(), [3, 3, 1, 1]);
+
+        let mut game = LifeGame::new(4, 2);
+        game.stamp(0, 0, &[(1, 0), (2, 0), (3, 1)]);
+        assert_eq!(game.quadrant_populations(), [1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn checksum() {
+        let mut a = LifeGame::new(5, 5);
+        let mut b = LifeGame::new(5, 5);
+        for &(x, y) in [(2, 1), (2, 2), (2, 3)].iter() {
+            a.set(x, y, true);
+            b.set(x, y, true);
+        }
+        assert_eq!(a.checksum(), b.checksum());
+
+        let before = a.checksum();
+        a.evolution();
+        assert_ne!(a.checksum(), before);
+        a.evolution();
+        assert_eq!(a.checksum(), before);
+    }
+
+    #[test]
+    fn population_min_max() {
+        /* Beacon
+         * ......    ......
+         * .oo...    .oo...
+         * .oo...    .o....
+         * ...oo. -> ....o.
+         * ...oo.    ...oo.
+         * ......    ......
+         */
+        let beacon = [(1, 1), (2, 1), (1, 2), (2, 2), (3, 3), (4, 3), (3, 4), (4, 4)];
+        let mut game = LifeGame::new(6, 6);
+        for &(x, y) in beacon.iter() {
+            game.set(x, y, true);
+        }
+        assert_eq!(game.population_min(), 0);
+        assert_eq!(game.population_max(), 8);
+
+        let world = (0..36).map(|i| beacon.contains(&(i % 6, i / 6)) as u8).collect();
+        let mut game = LifeGame::new_with_world(6, 6, world).unwrap();
+        assert_eq!(game.population_min(), 8);
+        assert_eq!(game.population_max(), 8);
+
+        for _ in 0..5 {
+            game.evolution();
+        }
+        assert_eq!(game.population_min(), 6);
+        assert_eq!(game.population_max(), 8);
+
+        game.reset();
+        assert_eq!(game.population_min(), 0);
+        assert_eq!(game.population_max(), 0);
+    }
+
+    #[test]
+    fn population_min_max_counts_edits_between_generations() {
+        let mut game = LifeGame::new(6, 6);
+        game.set(0, 0, true);
+        game.evolution();
+        game.set(1, 1, true);
+        game.set(4, 4, true);
+        assert_eq!(game.num_cells(), 2);
+        assert_eq!(game.population_min(), 0);
+        assert_eq!(game.population_max(), 2);
+
+        game.for_each_mut(|_, _, live| *live = true);
+        game.for_each_mut(|_, _, live| *live = false);
+        assert_eq!(game.population_max(), 36);
+    }
+
+    #[test]
+    fn will_be_alive() {
+        let mut game = LifeGame::new(5, 5);
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.set(3, 2, true);
+
+        assert_eq!(game.will_be_alive(2, 2), true);
+        assert_eq!(game.will_be_alive(1, 2), false);
+        assert_eq!(game.will_be_alive(2, 1), true);
+        assert_eq!(game.will_be_alive(0, 0), false);
+        assert_eq!(game.generation(), 0);
+        assert_eq!(game.get(1, 2), true);
+    }
+
+    #[test]
+    fn explain_cell() {
+        /* ........
+         * .oo...o.
+         * ..o.....
+         * ........
+         * ..o.....
+         * .ooo....
+         * ..o.....
+         * ........
+         */
+        let mut game = LifeGame::new(8, 8);
+        for &(x, y) in [(1, 1), (2, 1), (6, 1), (2, 2), (2, 4), (1, 5), (2, 5), (3, 5), (2, 6)].iter() {
+            game.set(x, y, true);
+        }
+
+        assert_eq!(game.explain_cell(2, 2),
+                   CellTransition { x: 2, y: 2, live: true, neighbors: 2, transition: Transition::Survive });
+        assert_eq!(game.explain_cell(1, 2).transition, Transition::Birth);
+        assert_eq!(game.explain_cell(6, 1).transition, Transition::Underpopulation);
+        assert_eq!(game.explain_cell(2, 5).transition, Transition::Overpopulation);
+        assert_eq!(game.explain_cell(6, 6).transition, Transition::StayDead);
+
+        game.set_wall(6, 1, true);
+        game.set_wall(1, 2, true);
+        assert_eq!(game.will_be_alive(6, 1), true);
+        assert_eq!(game.explain_cell(6, 1),
+                   CellTransition { x: 6, y: 1, live: true, neighbors: 0, transition: Transition::Wall });
+        assert_eq!(game.will_be_alive(1, 2), false);
+        assert_eq!(game.explain_cell(1, 2).transition, Transition::Wall);
+    }
+
+    #[test]
+    fn evolution_with_generation() {
+        /* .....    .....
+         * .....    ..o..
+         * .ooo. -> ..o..
+         * .....    ..o..
+         * .....    .....
+         */
+        let mut game = LifeGame::new(5, 5);
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.set(3, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(3, 0), false);
+        assert_eq!(game.get(4, 0), false);
+
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), true);
+        assert_eq!(game.get(3, 1), false);
+        assert_eq!(game.get(4, 1), false);
+
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), true);
+        assert_eq!(game.get(3, 2), false);
+        assert_eq!(game.get(4, 2), false);
+
+        assert_eq!(game.get(0, 3), false);
+        assert_eq!(game.get(1, 3), false);
+        assert_eq!(game.get(2, 3), true);
+        assert_eq!(game.get(3, 3), false);
+        assert_eq!(game.get(4, 3), false);
+
+        assert_eq!(game.get(0, 4), false);
+        assert_eq!(game.get(1, 4), false);
+        assert_eq!(game.get(2, 4), false);
+        assert_eq!(game.get(3, 4), false);
+        assert_eq!(game.get(4, 4), false);
+    }
+
+    #[test]
+    fn evolution_with_survival() {
+        /* ....      ....
+         * .oo.  ->  .oo.
          * .oo.  ->  .oo.
          * ....      ....
          */
-        let mut game = LifeGame::new(4, 4);
+        let mut game = LifeGame::new(4, 4);
+        game.set(1, 1, true);
+        game.set(2, 1, true);
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(3, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), true);
+        assert_eq!(game.get(2, 1), true);
+        assert_eq!(game.get(3, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), true);
+        assert_eq!(game.get(2, 2), true);
+        assert_eq!(game.get(3, 2), false);
+        assert_eq!(game.get(0, 3), false);
+        assert_eq!(game.get(1, 3), false);
+        assert_eq!(game.get(2, 3), false);
+        assert_eq!(game.get(3, 3), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_depopulation_0() {
+        /* ...      ...
+         * ...  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_depopulation_1() {
+        /* ...      ...
+         * .o.  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_depopulation_2() {
+        /* .o.      ...
+         * .o.  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(1, 0, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_depopulation_3() {
+        /* o..      ...
+         * .o.  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_1() {
+        /* ooo      ...
+         * oo.  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_2() {
+        /* ooo      ...
+         * ooo  ->  ...
+         * ...      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        game.set(2, 1, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_3() {
+        /* ooo      ...
+         * ooo  ->  ...
+         * o..      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        game.set(2, 1, true);
+        game.set(0, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_4() {
+        /* ooo      ...
+         * ooo  ->  ...
+         * oo.      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        game.set(2, 1, true);
+        game.set(0, 2, true);
+        game.set(1, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_5() {
+        /* ooo      ...
+         * ooo  ->  ...
+         * ooo      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(1, 1, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        game.set(2, 1, true);
+        game.set(0, 2, true);
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_with_dead_by_overpopulation_roll() {
+        /* ooo      ...
+         * ...  ->  ...
+         * oo.      ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 0, true);
+        game.set(0, 2, true);
+        game.set(1, 2, true);
+        game.evolution();
+
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.get(1, 0), false);
+        assert_eq!(game.get(2, 0), false);
+        assert_eq!(game.get(0, 1), false);
+        assert_eq!(game.get(1, 1), false);
+        assert_eq!(game.get(2, 1), false);
+        assert_eq!(game.get(0, 2), false);
+        assert_eq!(game.get(1, 2), false);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn generations_to_stabilize_to_block() {
+        /* ......    ......    ......
+         * .oo...    .oo...    .oo...
+         * .o.o.. -> .o.... -> .oo...
+         * ......    ......    ......
+         */
+        let mut game = LifeGame::new(6, 6);
         game.set(1, 1, true);
         game.set(2, 1, true);
         game.set(1, 2, true);
+        game.set(3, 2, true);
+        assert_eq!(game.generations_to_stabilize(10), Some(2));
+        assert_eq!(game.generations_to_stabilize(1), None);
+        assert_eq!(game.generation(), 0);
+        assert_eq!(game.get(3, 2), true);
+    }
+
+    #[test]
+    fn generations_to_stabilize_oscillator() {
+        let mut game = LifeGame::new(5, 5);
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.set(3, 2, true);
+        assert_eq!(game.generations_to_stabilize(10), Some(0));
+    }
+
+    #[test]
+    fn predecessors_of_block() {
+        /* oo.
+         * oo.
+         * ...
+         */
+        let mut game = LifeGame::new(3, 3);
+        for &(x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)].iter() {
+            game.set(x, y, true);
+        }
+
+        let found = game.predecessors(usize::MAX);
+        assert!(found.iter().any(|p| p.world == game.world));
+        for mut p in found {
+            p.evolution();
+            assert_eq!(p.world, game.world);
+        }
+        assert_eq!(game.predecessors(1).len(), 1);
+        assert_eq!(game.predecessors(0).len(), 0);
+    }
+
+    #[test]
+    fn predecessors_of_large_board() {
+        let game = LifeGame::new(5, 5);
+        assert_eq!(game.predecessors(1).len(), 0);
+    }
+
+    #[test]
+    fn predecessors_of_empty_board() {
+        let game = LifeGame::empty();
+        assert_eq!(game.predecessors(usize::MAX).len(), 0);
+        assert_eq!(game.has_predecessor(), Ok(false));
+    }
+
+    #[test]
+    fn evolution_autogrow_keeps_glider_moving() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut game = LifeGame::new(8, 8);
+        game.stamp(3, 3, &glider);
+        let fingerprint = game.pattern_fingerprint();
+
+        for _ in 0..40 {
+            game.evolution_autogrow(4);
+            assert_eq!(game.num_cells(), 5);
+        }
+        assert!(game.width() > 8);
+        assert_eq!(game.width(), game.height());
+        assert_eq!(game.generation(), 40);
+        assert_eq!(game.pattern_fingerprint(), fingerprint);
+
+        // The glider moves (+1, +1) every 4 generations.
+        let offset = (game.width() - 8) / 2;
+        let left = game.iter(Some(true)).map(|(x, _, _)| x).min().unwrap();
+        let top = game.iter(Some(true)).map(|(_, y, _)| y).min().unwrap();
+        assert_eq!((left, top), (3 + 10 + offset, 3 + 10 + offset));
+    }
+
+    #[test]
+    fn evolution_autogrow_interior_does_not_grow() {
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+        game.evolution_autogrow(4);
+        assert_eq!((game.width(), game.height()), (5, 5));
+        assert_eq!(game.generation(), 1);
+    }
+
+    #[test]
+    fn evolution_autogrow_radius_2() {
+        // (1, 5) is inside the radius-2 band without being on the edge.
+        let mut game = LifeGame::new(11, 11);
+        game.set_neighborhood_radius(2);
+        game.set(1, 5, true);
+        game.evolution_autogrow(1);
+        assert_eq!((game.width(), game.height()), (15, 15));
+
+        let mut game = LifeGame::new(11, 11);
+        game.set_neighborhood_radius(2);
+        game.set(2, 5, true);
+        game.evolution_autogrow(1);
+        assert_eq!((game.width(), game.height()), (11, 11));
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut game = LifeGame::new(11, 11);
+        game.set(5, 5, true);
+        game.shrink_to_fit(2);
+        assert_eq!((game.width(), game.height()), (5, 5));
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 2)]);
+
+        let mut game = LifeGame::new(10, 8);
+        game.stamp(3, 2, &[(0, 0), (1, 0), (2, 0)]);
+        game.shrink_to_fit(1);
+        assert_eq!((game.width(), game.height()), (5, 3));
+        let cells: Vec<(usize, usize)> = game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn shrink_to_fit_empty() {
+        let mut game = LifeGame::new(10, 8);
+        game.shrink_to_fit(0);
+        assert_eq!((game.width(), game.height()), (1, 1));
+
+        let mut game = LifeGame::new(10, 8);
+        game.set_neighborhood_radius(2);
+        game.shrink_to_fit(1);
+        assert_eq!((game.width(), game.height()), (5, 5));
+    }
+
+    #[test]
+    fn evolution_with_conway_closure() {
+        let mut a = LifeGame::new(8, 8);
+        a.stamp(1, 1, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        a.stamp(5, 5, &[(0, 0), (1, 0), (2, 0)]);
+        let mut b = LifeGame::new_with_world(8, 8, a.world.to_vec()).unwrap();
+
+        let count: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+        b.set_callback_mut(move |i| {
+            if i.is_evolution() {
+                *countcb.lock().unwrap() += 1;
+            }
+        });
+
+        for _ in 0..12 {
+            a.evolution();
+            b.evolution_with(|live, count| (count == 3) || (live && (count == 2)));
+            assert_eq!(a.world, b.world);
+        }
+        assert_eq!(b.generation(), 12);
+        assert_eq!(*count.lock().unwrap(), 12);
+    }
+
+    #[test]
+    fn evolution_with_custom_rule() {
+        // B1/S: every cell with exactly one neighbor is born, nothing survives.
+        let mut game = LifeGame::new(5, 5);
+        game.set(2, 2, true);
+        game.evolution_with(|live, count| !live && (count == 1));
+        assert_eq!(game.num_cells(), 8);
+        assert_eq!(game.get(2, 2), false);
+    }
+
+    #[test]
+    fn evolution_noisy() {
+        let seed: &[usize] = &[1, 2, 3];
+        let mut rng = StdRng::from_seed(seed);
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        let mut noisy = LifeGame::new(8, 8);
+        let mut plain = LifeGame::new(8, 8);
+        noisy.stamp(1, 1, &glider);
+        plain.stamp(1, 1, &glider);
+        noisy.set_noise(0.0);
+        for _ in 0..8 {
+            noisy.evolution_noisy(&mut rng);
+            plain.evolution();
+            assert_eq!(noisy.world, plain.world);
+        }
+
+        noisy.set_noise(1.0);
+        noisy.evolution_noisy(&mut rng);
+        assert_eq!(noisy.num_cells(), 0);
+        assert_eq!(noisy.generation(), 9);
+    }
+
+    #[test]
+    fn reset_border() {
+        let seed: &[usize] = &[4, 5, 6];
+        let mut rng = StdRng::from_seed(seed);
+        let mut game = LifeGame::new(10, 8);
+        for _ in 0..10 {
+            game.for_each_mut(|_, _, live| *live = true);
+            game.reset_border(0.5, &mut rng);
+            assert!(game.num_cells() > 0);
+            for (x, y, _) in game.iter(Some(true)) {
+                assert!((x == 0) || (y == 0) || (x == 9) || (y == 7));
+            }
+        }
+
+        game.reset_border(1.0, &mut rng);
+        assert_eq!(game.num_cells(), (2 * 10) + (2 * 6));
+    }
+
+    #[test]
+    fn set_seed() {
+        let mut a = LifeGame::new(16, 16);
+        let mut b = LifeGame::new(16, 16);
+        a.set_seed(42);
+        b.set_seed(42);
+        let mut previous = Vec::new();
+        for _ in 0..3 {
+            a.reset_by_rand();
+            b.reset_by_rand();
+            assert_eq!(a.to_bytes(), b.to_bytes());
+            assert_ne!(a.to_bytes(), previous);
+            previous = a.to_bytes();
+        }
+
+        a.set_noise(0.2);
+        b.set_noise(0.2);
+        for _ in 0..3 {
+            a.reset_by_rand();
+            b.reset_by_rand();
+            for _ in 0..4 {
+                a.evolution_noisy_by_rand();
+                b.evolution_noisy_by_rand();
+            }
+            a.reset_border_by_rand(0.5);
+            b.reset_border_by_rand(0.5);
+            a.evolution_noisy_by_rand();
+            b.evolution_noisy_by_rand();
+            assert_eq!(a.to_bytes(), b.to_bytes());
+        }
+
+        let mut c = LifeGame::new(16, 16);
+        c.set_seed(43);
+        c.reset_by_rand();
+        a.set_seed(42);
+        a.reset_by_rand();
+        assert_ne!(a.to_bytes(), c.to_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "Density must be between 0 and 1.")]
+    fn reset_border_density_over_1() {
+        let mut game = LifeGame::new(4, 4);
+        game.reset_border(1.5, &mut rand::thread_rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "Noise must be between 0 and 1.")]
+    fn set_noise_over_1() {
+        let mut game = LifeGame::new(5, 5);
+        game.set_noise(1.5);
+    }
+
+    #[test]
+    fn evolution_incremental_matches_evolution() {
+        let mut a = LifeGame::new(20, 16);
+        let mut b = LifeGame::new(20, 16);
+        for y in 0..16 {
+            for x in 0..20 {
+                let live = ((x * 7) + (y * 13)) % 5 == 0;
+                a.set(x, y, live);
+                b.set(x, y, live);
+            }
+        }
+        a.set_wall(4, 4, true);
+        b.set_wall(4, 4, true);
+
+        for generation in 0..30 {
+            if generation == 15 {
+                a.stamp(8, 8, &[(0, 0), (1, 0), (2, 0)]);
+                b.stamp(8, 8, &[(0, 0), (1, 0), (2, 0)]);
+            }
+            a.evolution();
+            b.evolution_incremental();
+            assert_eq!(a.world, b.world);
+            assert_eq!(a.generation(), b.generation());
+        }
+    }
+
+    #[test]
+    fn tick_while_paused_and_running() {
+        let mut game = LifeGame::new(5, 5);
+        assert_eq!(game.is_running(), true);
+
+        game.pause();
+        assert_eq!(game.is_running(), false);
+        assert_eq!(game.tick(), false);
+        assert_eq!(game.generation(), 0);
+
+        game.resume();
+        assert_eq!(game.is_running(), true);
+        assert_eq!(game.tick(), true);
+        assert_eq!(game.generation(), 1);
+    }
+
+    #[test]
+    fn has_predecessor() {
+        let mut game = LifeGame::new(3, 3);
+        for &(x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)].iter() {
+            game.set(x, y, true);
+        }
+        assert_eq!(game.has_predecessor(), Ok(true));
+
+        // A lone cell cannot be reached on a 3x3 torus.
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        assert_eq!(game.has_predecessor(), Ok(false));
+
+        let game = LifeGame::new(5, 5);
+        assert_eq!(game.has_predecessor(), Err(SearchError::TooLarge { cells: 25 }));
+    }
+
+    #[test]
+    fn last_dirty_rect() {
+        /* ......    ......
+         * ......    ..o...
+         * .ooo.. -> ..o...
+         * ......    ..o...
+         * ......    ......
+         */
+        let mut game = LifeGame::new(6, 5);
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(game.last_dirty_rect(), None);
+
+        game.evolution();
+        assert_eq!(game.last_dirty_rect(), Some((1, 1, 3, 3)));
+
+        let mut game = LifeGame::new(4, 4);
+        game.stamp(1, 1, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        game.evolution();
+        assert_eq!(game.last_dirty_rect(), None);
+    }
+
+    #[test]
+    fn evolution_frames() {
+        let mut game = LifeGame::new(5, 5);
+        game.set(1, 2, true);
         game.set(2, 2, true);
+        game.set(3, 2, true);
+        let frames = game.evolution_frames(3);
+
+        assert_eq!(frames.len(), 3);
+        for (i, frame) in frames.iter().enumerate() {
+            let vertical = i % 2 == 0;
+            assert_eq!(frame[1][2], vertical);
+            assert_eq!(frame[2][2], true);
+            assert_eq!(frame[3][2], vertical);
+            assert_eq!(frame[2][1], !vertical);
+            assert_eq!(frame[2][3], !vertical);
+            assert_eq!(frame.iter().flatten().filter(|&&live| live).count(), 3);
+        }
+        assert_eq!(game.generation(), 3);
+    }
+
+    #[test]
+    fn downscale() {
+        let mut game = LifeGame::new(4, 4);
+        game.for_each_mut(|_, _, live| *live = true);
+        assert_eq!(game.downscale(2), vec![vec![4, 4], vec![4, 4]]);
+    }
+
+    #[test]
+    fn downscale_partial_block() {
+        /* o.o.o
+         * ..o..
+         * o...o
+         */
+        let mut game = LifeGame::new(5, 3);
+        game.set(0, 0, true);
+        game.set(2, 0, true);
+        game.set(4, 0, true);
+        game.set(2, 1, true);
+        game.set(0, 2, true);
+        game.set(4, 2, true);
+        assert_eq!(game.downscale(2), vec![vec![1, 2, 1], vec![1, 0, 1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Factor must be not 0.")]
+    fn downscale_by_0() {
+        let game = LifeGame::new(4, 4);
+        game.downscale(0);
+    }
+
+    #[test]
+    fn density_grid_half_live() {
+        let mut game = LifeGame::new(4, 4);
+        game.for_each_mut(|x, y, live| *live = (x + y) % 2 == 0);
+        assert_eq!(game.density_grid(2), vec![vec![0.5, 0.5], vec![0.5, 0.5]]);
+    }
+
+    #[test]
+    fn density_grid_partial_tile() {
+        /* o.o.o
+         * ..o..
+         * o...o
+         */
+        let mut game = LifeGame::new(5, 3);
+        game.set(0, 0, true);
+        game.set(2, 0, true);
+        game.set(4, 0, true);
+        game.set(2, 1, true);
+        game.set(0, 2, true);
+        game.set(4, 2, true);
+        assert_eq!(game.density_grid(2), vec![vec![0.25, 0.5, 0.5], vec![0.5, 0.0, 1.0]]);
+    }
+
+    #[test]
+    fn evolution_changed() {
+        let mut block = LifeGame::new(4, 4);
+        block.set(1, 1, true);
+        block.set(2, 1, true);
+        block.set(1, 2, true);
+        block.set(2, 2, true);
+        assert_eq!(block.evolution_changed(), false);
+        assert_eq!(block.generation(), 1);
+
+        let mut blinker = LifeGame::new(5, 5);
+        blinker.set(1, 2, true);
+        blinker.set(2, 2, true);
+        blinker.set(3, 2, true);
+        assert_eq!(blinker.evolution_changed(), true);
+        assert_eq!(blinker.evolution_changed(), true);
+    }
+
+    #[test]
+    fn step_for() {
+        let mut game = LifeGame::new(500, 500);
+        let steps = game.step_for(1000, Duration::from_nanos(1));
+        assert!(steps >= 1);
+        assert!(steps < 1000);
+        assert_eq!(game.generation(), steps);
+
+        assert_eq!(game.step_for(3, Duration::from_secs(60)), 3);
+        assert_eq!(game.step_for(0, Duration::from_secs(60)), 0);
+    }
+
+    #[test]
+    fn next_world_sliding_matches_neighbor_counts() {
+        let seed: &[usize] = &[7, 8, 9];
+        let mut rng = StdRng::from_seed(seed);
+        for &(width, height) in [(1, 1), (2, 3), (37, 23)].iter() {
+            let mut world: Vec<u8> = (0..(width * height)).map(|_| rng.gen::<bool>() as u8).collect();
+            for _ in 0..50 {
+                let expected: Vec<u8> = (0..(width * height)).map(|i| {
+                    let count = LifeGame::count_neighbors(&world, width, height, 1, i % width, i / width);
+                    LifeGame::next_state(world[i] > 0, count)
+                }).collect();
+                let next = LifeGame::next_world_sliding(&world, width, height);
+                assert_eq!(next, expected);
+                world = next;
+            }
+        }
+    }
+
+    #[test]
+    fn evolve_while() {
+        /* R-pentomino
+         * .oo
+         * oo.
+         * .o.
+         */
+        let mut game = LifeGame::new(30, 30);
+        game.set(15, 14, true);
+        game.set(16, 14, true);
+        game.set(14, 15, true);
+        game.set(15, 15, true);
+        game.set(15, 16, true);
+
+        let steps = game.evolve_while(100, |g| g.num_cells() <= 10);
+        assert!(steps > 0);
+        assert!(steps < 100);
+        assert_eq!(game.generation(), steps);
+        assert!(game.num_cells() > 10);
+
+        assert_eq!(game.evolve_while(5, |_| true), 5);
+        assert_eq!(game.evolve_while(5, |_| false), 0);
+    }
+
+    #[test]
+    fn generation_default_is_0() {
+        let game = LifeGame::new(1, 1);
+        assert_eq!(game.generation(), 0);
+    }
+
+    #[test]
+    fn generation_is_0_after_reset() {
+        let mut game = LifeGame::new(1, 1);
+        game.evolution();
+        game.reset();
+        assert_eq!(game.generation(), 0);
+    }
+
+    #[test]
+    fn generation_is_0_after_reset_by_rand() {
+        let mut game = LifeGame::new(1, 1);
+        game.evolution();
+        game.reset_by_rand();
+        assert_eq!(game.generation(), 0);
+    }
+
+    #[test]
+    fn evolve_all_advances_each_game() {
+        let mut games = vec![LifeGame::new(3, 3), LifeGame::new(4, 4), LifeGame::new(5, 5)];
+        games[2].set(1, 2, true);
+        games[2].set(2, 2, true);
+        games[2].set(3, 2, true);
+
+        evolve_all(&mut games);
+
+        for game in games.iter() {
+            assert_eq!(game.generation(), 1);
+        }
+        assert_eq!(games[2].get(2, 1), true);
+        assert_eq!(games[2].get(2, 2), true);
+        assert_eq!(games[2].get(2, 3), true);
+        assert_eq!(games[2].get(1, 2), false);
+    }
+
+    #[test]
+    fn callback() {
+        let info: Arc<Mutex<Option<CallbackInfo>>> = Arc::new(Mutex::new(None));
+        let infocb = info.clone();
+
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |i| {
+                            let mut info = infocb.lock().unwrap();
+                            *info = Some(i);
+                        });
+        {
+            let info = info.lock().unwrap();
+            assert_eq!(*info, None);
+        }
+
+        game.set(0, 0, true);
+        {
+            let info = info.lock().unwrap();
+            assert_eq!(*info,
+                       Some(CallbackInfo {
+                               event: CallbackEvent::Set,
+                               generation: 0,
+                               width: game.width(),
+                               height: game.height(),
+                               num_cells: 1,
+                               cell: Some(CellInfo{ x:0, y:0, live:true, neighbors:None }),
+                               label: None
+                       }));
+        }
+
         game.evolution();
+        {
+            let info = info.lock().unwrap();
+            assert_eq!(*info,
+                       Some(CallbackInfo {
+                               event: CallbackEvent::Evolution,
+                               generation: 1,
+                               width: game.width(),
+                               height: game.height(),
+                               num_cells: 0,
+                               cell: None,
+                               label: None
+                       }));
+        }
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(3, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), true);
-        assert_eq!(game.get(2, 1), true);
-        assert_eq!(game.get(3, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), true);
-        assert_eq!(game.get(2, 2), true);
-        assert_eq!(game.get(3, 2), false);
-        assert_eq!(game.get(0, 3), false);
-        assert_eq!(game.get(1, 3), false);
-        assert_eq!(game.get(2, 3), false);
-        assert_eq!(game.get(3, 3), false);
+        game.reset();
+        {
+            let info = info.lock().unwrap();
+            assert_eq!(*info,
+                       Some(CallbackInfo {
+                               event: CallbackEvent::Reset,
+                               generation: 0,
+                               width: game.width(),
+                               height: game.height(),
+                               num_cells: 0,
+                               cell: None,
+                               label: None
+                       }));
+        }
+
+        game.evolution();
+        game.reset_by_rand();
+        {
+            let num_cells = game.num_cells();
+            let info = info.lock().unwrap();
+            assert_eq!(*info,
+                       Some(CallbackInfo {
+                               event: CallbackEvent::Reset,
+                               generation: 0,
+                               width: game.width(),
+                               height: game.height(),
+                               num_cells,
+                               cell: None,
+                               label: None
+                       }));
+        }
     }
 
     #[test]
-    fn evolution_with_dead_by_depopulation_0() {
-        /* ...      ...
-         * ...  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.evolution();
+    fn callback_reports_label() {
+        let infos: Arc<Mutex<Vec<CallbackInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        let infoscb = infos.clone();
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |i| {
+                            infoscb.lock().unwrap().push(i);
+                        });
+        game.set_label(3, "checkpoint".to_string());
+        game.set(0, 0, true);
+        for _ in 0..4 {
+            game.evolution();
+        }
+
+        let infos = infos.lock().unwrap();
+        let labels: Vec<(usize, Option<String>)> = infos.iter()
+            .filter(|i| i.is_evolution())
+            .map(|i| (i.generation, i.label.clone()))
+            .collect();
+        assert_eq!(labels, vec![(1, None), (2, None), (3, Some("checkpoint".to_string())), (4, None)]);
+        assert_eq!(infos[0].label, None);
     }
 
     #[test]
-    fn evolution_with_dead_by_depopulation_1() {
-        /* ...      ...
-         * .o.  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
-        game.evolution();
+    fn set_callback_mut() {
+        struct Holder {
+            game: LifeGame
+        }
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+
+        let mut holder = Holder { game: LifeGame::new(3, 3) };
+        {
+            let game = &mut holder.game;
+            game.set_callback_mut(move |_| {
+                *countcb.lock().unwrap() += 1;
+            });
+            game.set(0, 0, true);
+            game.evolution();
+        }
+        assert_eq!(*count.lock().unwrap(), 2);
     }
 
     #[test]
-    fn evolution_with_dead_by_depopulation_2() {
-        /* .o.      ...
-         * .o.  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
-        game.set(1, 0, true);
-        game.evolution();
+    fn scoped_run_with_borrowed_counter() {
+        use std::cell::Cell;
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |_| {
+                            *countcb.lock().unwrap() += 1;
+                        });
+
+        let scoped = Cell::new(0);
+        let mut events = Vec::new();
+        game.scoped_run(|i| {
+            scoped.set(scoped.get() + 1);
+            events.push(i.event);
+        }, |run| {
+            run.set(0, 0, true);
+            assert_eq!(scoped.get(), 1);
+            run.evolution();
+            assert_eq!(scoped.get(), 2);
+            let generation = run.with(|game| { game.reset(); game.generation() });
+            assert_eq!(generation, 0);
+            assert_eq!(scoped.get(), 3);
+            assert_eq!(run.game().num_cells(), 0);
+        });
+        assert_eq!(events, vec![CallbackEvent::Set, CallbackEvent::Evolution, CallbackEvent::Reset]);
+        assert_eq!(*count.lock().unwrap(), 0);
+
+        game.evolution();
+        assert_eq!(scoped.get(), 3);
+        assert_eq!(*count.lock().unwrap(), 1);
     }
 
     #[test]
-    fn evolution_with_dead_by_depopulation_3() {
-        /* o..      ...
-         * .o.  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
+    fn scoped_run_restores_callback_on_panic() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+        let mut game = LifeGame::new(3, 3)
+                        .set_callback(move |_| {
+                            *countcb.lock().unwrap() += 1;
+                        });
+
+        let mut scoped = 0;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            game.scoped_run(|_| scoped += 1, |run| {
+                run.set(0, 0, true);
+                panic!("body failed");
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(scoped, 1);
+        assert_eq!(*count.lock().unwrap(), 0);
+
         game.set(1, 1, true);
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn region_callback() {
+        let cells: Arc<Mutex<Vec<(usize, usize)>>> = Arc::new(Mutex::new(Vec::new()));
+        let cellscb = cells.clone();
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+
+        let mut game = LifeGame::new(10, 10)
+                        .set_callback(move |_| {
+                            *countcb.lock().unwrap() += 1;
+                        });
+        let id = game.add_region_callback((2, 2, 4, 3), move |i| {
+            let cell = i.cell.unwrap();
+            cellscb.lock().unwrap().push((cell.x, cell.y));
+        });
         game.set(0, 0, true);
+        game.set(5, 3, true);
+        game.set(2, 2, true);
+        game.set(4, 3, true);
         game.evolution();
+        assert_eq!(*cells.lock().unwrap(), vec![(2, 2), (4, 3)]);
+        assert_eq!(*count.lock().unwrap(), 5);
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        assert_eq!(game.remove_region_callback(id), true);
+        assert_eq!(game.remove_region_callback(id), false);
+        game.set(3, 3, true);
+        assert_eq!(cells.lock().unwrap().len(), 2);
     }
 
+    #[cfg(feature = "send-callbacks")]
     #[test]
-    fn evolution_with_dead_by_overpopulation_1() {
-        /* ooo      ...
-         * oo.  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
+    fn send_to_thread() {
+        let count = Arc::new(Mutex::new(0));
+        let countcb = count.clone();
+        let mut game = LifeGame::new(5, 5)
+                        .set_callback(move |_| {
+                            *countcb.lock().unwrap() += 1;
+                        });
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+
+        let game = std::thread::spawn(move || {
+            game.evolution();
+            game
+        }).join().unwrap();
+        assert_eq!(game.generation(), 1);
+        assert_eq!(game.get(2, 1), true);
+        assert_eq!(*count.lock().unwrap(), 4);
+    }
+
+    #[test]
+    fn callback_reports_neighbors() {
+        let info: Arc<Mutex<Option<CallbackInfo>>> = Arc::new(Mutex::new(None));
+        let infocb = info.clone();
+
+        let mut game = LifeGame::new(4, 4)
+                        .set_callback(move |i| {
+                            *infocb.lock().unwrap() = Some(i);
+                        });
         game.set(0, 0, true);
         game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 1, true);
-        game.evolution();
+        game.set_report_neighbors(true);
+        game.set(1, 1, true);
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let info = info.lock().unwrap().clone().unwrap();
+        assert_eq!(info.cell, Some(CellInfo { x: 1, y: 1, live: true, neighbors: Some(2) }));
+        assert_eq!(game.live_neighbors(1, 1), 2);
     }
 
     #[test]
-    fn evolution_with_dead_by_overpopulation_2() {
-        /* ooo      ...
-         * ooo  ->  ...
-         * ...      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
+    fn callback_info_predicates() {
+        let infos: Arc<Mutex<Vec<CallbackInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        let infoscb = infos.clone();
+
+        let mut game = LifeGame::new(4, 4)
+                        .set_callback(move |i| {
+                            infoscb.lock().unwrap().push(i);
+                        });
+        game.reset();
         game.set(0, 0, true);
-        game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 1, true);
-        game.set(2, 1, true);
         game.evolution();
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let infos = infos.lock().unwrap();
+        let predicates: Vec<(bool, bool, bool)> = infos.iter()
+            .map(|i| (i.is_reset(), i.is_set(), i.is_evolution()))
+            .collect();
+        assert_eq!(predicates, vec![(true, false, false), (false, true, false), (false, false, true)]);
     }
 
     #[test]
-    fn evolution_with_dead_by_overpopulation_3() {
-        /* ooo      ...
-         * ooo  ->  ...
-         * o..      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
+    fn pretty_print() {
+        let mut game = LifeGame::new(3, 2);
         game.set(0, 0, true);
-        game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 1, true);
         game.set(2, 1, true);
-        game.set(0, 2, true);
-        game.evolution();
+        assert_eq!(game.pretty_print(), "  0 1 2\n0 o . .\n1 . . o\n");
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let mut game = LifeGame::new(11, 2);
+        game.set(10, 1, true);
+        let s = game.pretty_print();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[0], "   0  1  2  3  4  5  6  7  8  9 10");
+        assert_eq!(lines[2], "1  .  .  .  .  .  .  .  .  .  .  o");
     }
 
     #[test]
-    fn evolution_with_dead_by_overpopulation_4() {
-        /* ooo      ...
-         * ooo  ->  ...
-         * oo.      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
-        game.set(0, 0, true);
+    fn to_svg() {
+        let mut game = LifeGame::new(4, 3);
         game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 1, true);
-        game.set(2, 1, true);
-        game.set(0, 2, true);
-        game.set(1, 2, true);
-        game.evolution();
+        game.set(3, 2, true);
+        let svg = game.to_svg(10, "black", "white");
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("viewBox=\"0 0 40 30\""));
+        assert!(svg.contains("<rect width=\"40\" height=\"30\" fill=\"white\"/>"));
+        assert_eq!(svg.matches("fill=\"black\"").count(), 2);
+        assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"black\"/>"));
+        assert!(svg.contains("<rect x=\"30\" y=\"20\" width=\"10\" height=\"10\" fill=\"black\"/>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn to_svg_escapes_colors() {
+        let mut game = LifeGame::new(1, 1);
+        game.set(0, 0, true);
+        let svg = game.to_svg(1, "\"/><script>", "a&b");
+
+        assert!(svg.contains("fill=\"&quot;/>&lt;script>\""));
+        assert!(svg.contains("fill=\"a&amp;b\""));
+        assert!(!svg.contains("<script"));
+    }
+
+    #[test]
+    fn find_pattern() {
+        let block = [(0, 0), (1, 0), (0, 1), (1, 1)];
+        let mut game = LifeGame::new(10, 8);
+        game.stamp(1, 1, &block);
+        game.stamp(6, 4, &block);
+        assert_eq!(game.find_pattern(&block, 2, 2), vec![(1, 1), (6, 4)]);
+
+        let bordered = [(1, 1), (2, 1), (1, 2), (2, 2)];
+        assert_eq!(game.find_pattern(&bordered, 4, 4), vec![(0, 0), (5, 3)]);
+
+        game.set(9, 7, true);
+        game.set(0, 7, true);
+        game.set(9, 0, true);
+        game.set(0, 0, true);
+        assert_eq!(game.find_pattern(&block, 2, 2), vec![(1, 1), (6, 4), (9, 7)]);
+        assert_eq!(game.find_pattern(&block, 11, 2), vec![]);
+        assert_eq!(game.find_pattern(&[], 0, 2), vec![]);
+        assert_eq!(game.find_pattern(&[], 2, 0), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pattern must be inside its bounding box.")]
+    fn find_pattern_cell_outside_bounding_box() {
+        let game = LifeGame::new(10, 8);
+        game.find_pattern(&[(0, 0), (2, 0)], 2, 1);
+    }
+
+    #[test]
+    fn touched_boundary() {
+        let mut game = LifeGame::new(5, 4);
+        game.set(1, 1, true);
+        game.set(3, 2, true);
+        assert_eq!(game.touched_boundary(), false);
+
+        for &(x, y) in [(0, 2), (4, 1), (2, 0), (2, 3)].iter() {
+            let mut game = LifeGame::new(5, 4);
+            game.set(x, y, true);
+            assert_eq!(game.touched_boundary(), true);
+        }
     }
 
     #[test]
-    fn evolution_with_dead_by_overpopulation_5() {
-        /* ooo      ...
-         * ooo  ->  ...
-         * ooo      ...
-         */
-        let mut game = LifeGame::new(3, 3);
-        game.set(1, 1, true);
+    fn live_set() {
+        let mut game = LifeGame::new(4, 3);
         game.set(0, 0, true);
-        game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 1, true);
-        game.set(2, 1, true);
-        game.set(0, 2, true);
+        game.set(3, 1, true);
         game.set(1, 2, true);
-        game.set(2, 2, true);
-        game.evolution();
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
+        let expected: HashSet<(usize, usize)> = [(0, 0), (3, 1), (1, 2)].iter().cloned().collect();
+        assert_eq!(game.live_set(), expected);
     }
 
     #[test]
-    fn evolution_with_dead_by_overpopulation_roll() {
-        /* ooo      ...
-         * ...  ->  ...
-         * oo.      ...
+    fn watch_cell() {
+        /* .....      .....
+         * .....      ..o..
+         * .ooo.  ->  ..o..
+         * .....      ..o..
+         * .....      .....
          */
-        let mut game = LifeGame::new(3, 3);
-        game.set(0, 0, true);
-        game.set(1, 0, true);
-        game.set(2, 0, true);
-        game.set(0, 2, true);
+        let mut game = LifeGame::new(5, 5);
         game.set(1, 2, true);
-        game.evolution();
+        game.set(2, 2, true);
+        game.set(3, 2, true);
+        assert_eq!(game.cell_timeline(), &[] as &[bool]);
 
-        assert_eq!(game.get(0, 0), false);
-        assert_eq!(game.get(1, 0), false);
-        assert_eq!(game.get(2, 0), false);
-        assert_eq!(game.get(0, 1), false);
-        assert_eq!(game.get(1, 1), false);
-        assert_eq!(game.get(2, 1), false);
-        assert_eq!(game.get(0, 2), false);
-        assert_eq!(game.get(1, 2), false);
-        assert_eq!(game.get(2, 2), false);
-    }
+        game.watch_cell(2, 1);
+        for _ in 0..4 {
+            game.evolution();
+        }
+        assert_eq!(game.cell_timeline(), &[false, true, false, true, false]);
 
-    #[test]
-    fn generation_default_is_0() {
-        let game = LifeGame::new(1, 1);
-        assert_eq!(game.generation(), 0);
+        game.watch_cell(2, 2);
+        game.evolution();
+        assert_eq!(game.cell_timeline(), &[true, true]);
     }
 
     #[test]
-    fn generation_is_0_after_reset() {
-        let mut game = LifeGame::new(1, 1);
-        game.evolution();
-        game.reset();
-        assert_eq!(game.generation(), 0);
+    #[should_panic(expected = "Cell must be inside the world.")]
+    fn watch_cell_over_width() {
+        let mut game = LifeGame::new(5, 5);
+        game.watch_cell(5, 0);
     }
 
     #[test]
-    fn generation_is_0_after_reset_by_rand() {
-        let mut game = LifeGame::new(1, 1);
+    fn replay() {
+        let infos: Arc<Mutex<Vec<CallbackInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        let infoscb = infos.clone();
+
+        let mut game = LifeGame::new(5, 5)
+                        .set_callback(move |i| {
+                            infoscb.lock().unwrap().push(i);
+                        });
+        game.set(4, 4, true);
+        game.reset();
+        game.set(1, 2, true);
+        game.set(2, 2, true);
+        game.set(3, 2, true);
+        game.set(0, 0, true);
+        game.set(0, 0, false);
         game.evolution();
-        game.reset_by_rand();
-        assert_eq!(game.generation(), 0);
+
+        let mut replayed = LifeGame::new(5, 5);
+        assert_eq!(replayed.replay(&infos.lock().unwrap()), Ok(()));
+        replayed.evolution();
+        assert_eq!(replayed.to_bytes(), game.to_bytes());
+        assert_eq!(replayed.get(2, 1), true);
+        assert_eq!(replayed.get(4, 4), false);
     }
 
     #[test]
-    fn callback() {
-        let info: Arc<Mutex<Option<CallbackInfo>>> = Arc::new(Mutex::new(None));
-        let infocb = info.clone();
+    fn replay_rejects_unrecorded_cells() {
+        let infos: Arc<Mutex<Vec<CallbackInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        let infoscb = infos.clone();
 
-        let mut game = LifeGame::new(3, 3)
+        let mut game = LifeGame::new(3, 2)
                         .set_callback(move |i| {
-                            let mut info = infocb.lock().unwrap();
-                            *info = Some(i);
+                            infoscb.lock().unwrap().push(i);
                         });
-        {
-            let info = info.lock().unwrap();
-            assert_eq!(*info, None);
-        }
+        game.set(1, 0, true);
+        game.set(0, 1, true);
+        game.transpose();
+
+        let mut replayed = LifeGame::new(3, 2);
+        replayed.set(2, 1, true);
+        assert_eq!(replayed.replay(&infos.lock().unwrap()),
+                   Err(ReplayError::SizeMismatch { index: 2, width: 2, height: 3 }));
+        let cells: Vec<(usize, usize)> = replayed.iter(Some(true)).map(|(x, y, _)| (x, y)).collect();
+        assert_eq!(cells, vec![(2, 1)]);
 
+        let mut replayed = LifeGame::new(2, 3);
+        assert_eq!(replayed.replay(&infos.lock().unwrap()),
+                   Err(ReplayError::SizeMismatch { index: 0, width: 3, height: 2 }));
+
+        infos.lock().unwrap().clear();
+        let mut game = LifeGame::new(5, 5)
+                        .set_callback({
+                            let infoscb = infos.clone();
+                            move |i| infoscb.lock().unwrap().push(i)
+                        });
         game.set(0, 0, true);
-        {
-            let info = info.lock().unwrap();
-            assert_eq!(*info,
-                       Some(CallbackInfo {
-                               event: CallbackEvent::Set,
-                               generation: 0,
-                               width: game.width(),
-                               height: game.height(),
-                               num_cells: 1,
-                               cell: Some(CellInfo{ x:0, y:0, live:true })
-                       }));
-        }
+        game.set(1, 0, true);
+        game.shift(1, 1, false);
 
-        game.evolution();
-        {
-            let info = info.lock().unwrap();
-            assert_eq!(*info,
-                       Some(CallbackInfo {
-                               event: CallbackEvent::Evolution,
-                               generation: 1,
-                               width: game.width(),
-                               height: game.height(),
-                               num_cells: 0,
-                               cell: None
-                       }));
+        let mut replayed = LifeGame::new(5, 5);
+        assert_eq!(replayed.replay(&infos.lock().unwrap()),
+                   Err(ReplayError::UnreplayableReset { index: 2, num_cells: 2 }));
+        assert_eq!(replayed.num_cells(), 0);
+    }
+
+    #[test]
+    fn count_clusters() {
+        /* oo.....
+         * oo.....
+         * ...oo..
+         * ...oo..
+         * .......
+         */
+        let mut blocks = LifeGame::new(7, 5);
+        blocks.stamp(0, 0, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        blocks.stamp(3, 2, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(blocks.count_clusters(false), 2);
+        assert_eq!(blocks.count_clusters(true), 2);
+
+        let mut pair = LifeGame::new(5, 5);
+        pair.set(1, 1, true);
+        pair.set(2, 2, true);
+        assert_eq!(pair.count_clusters(false), 2);
+        assert_eq!(pair.count_clusters(true), 1);
+
+        let mut wrapped = LifeGame::new(5, 5);
+        wrapped.set(0, 2, true);
+        wrapped.set(4, 2, true);
+        assert_eq!(wrapped.count_clusters(false), 1);
+
+        assert_eq!(LifeGame::new(5, 5).count_clusters(true), 0);
+    }
+
+    #[test]
+    fn largest_cluster() {
+        /* .......
+         * .oo....
+         * .oo....
+         * .......
+         * .....o.
+         */
+        let mut game = LifeGame::new(7, 5);
+        game.stamp(1, 1, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        game.set(5, 4, true);
+        assert_eq!(game.largest_cluster(true), vec![(1, 1), (2, 1), (1, 2), (2, 2)]);
+        assert_eq!(LifeGame::new(7, 5).largest_cluster(true), vec![]);
+    }
+
+    #[test]
+    fn symmetry() {
+        /* Pentadecathlon phase: a row of 10 cells
+         * ............
+         * ............
+         * .oooooooooo.
+         * ............
+         * ............
+         */
+        let mut row = LifeGame::new(12, 5);
+        for x in 1..=10 {
+            row.set(x, 2, true);
         }
+        assert_eq!(row.symmetry(), SymmetrySet { horizontal: true, vertical: true, rotation_180: true, diagonal: false });
 
-        game.reset();
-        {
-            let info = info.lock().unwrap();
-            assert_eq!(*info,
-                       Some(CallbackInfo {
-                               event: CallbackEvent::Reset,
-                               generation: 0,
-                               width: game.width(),
-                               height: game.height(),
-                               num_cells: 0,
-                               cell: None
-                       }));
+        /* ..o..
+         * ..o..
+         * ooooo
+         * ..o..
+         * ..o..
+         */
+        let mut plus = LifeGame::new(5, 5);
+        for i in 0..5 {
+            plus.set(i, 2, true);
+            plus.set(2, i, true);
         }
+        assert_eq!(plus.symmetry(), SymmetrySet { horizontal: true, vertical: true, rotation_180: true, diagonal: true });
 
-        game.evolution();
-        game.reset_by_rand();
-        {
-            let num_cells = game.num_cells();
-            let info = info.lock().unwrap();
-            assert_eq!(*info,
-                       Some(CallbackInfo {
-                               event: CallbackEvent::Reset,
-                               generation: 0,
-                               width: game.width(),
-                               height: game.height(),
-                               num_cells: num_cells,
-                               cell: None
-                       }));
+        let mut glider = LifeGame::new(8, 8);
+        glider.stamp(1, 1, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(glider.symmetry(), SymmetrySet::default());
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        /* Two blinkers crossing at the center
+         * .....    .....
+         * .....    ..o..
+         * .ooo.    ..o..
+         * .....    ..o..
+         * .....    .....
+         */
+        let mut a = LifeGame::new(5, 5);
+        let mut b = LifeGame::new(5, 5);
+        for i in 1..4 {
+            a.set(i, 2, true);
+            b.set(2, i, true);
         }
+
+        let union = a.union(&b).unwrap();
+        let expected: HashSet<(usize, usize)> =
+            [(1, 2), (2, 2), (3, 2), (2, 1), (2, 3)].iter().cloned().collect();
+        assert_eq!(union.live_set(), expected);
+
+        let intersection = a.intersection(&b).unwrap();
+        let expected: HashSet<(usize, usize)> = [(2, 2)].iter().cloned().collect();
+        assert_eq!(intersection.live_set(), expected);
+
+        assert_eq!(a.union(&LifeGame::new(5, 4)).err(),
+                   Some(DimensionError::SizeMismatch { width: 5, height: 5, other_width: 5, other_height: 4 }));
+        assert_eq!(a.intersection(&LifeGame::new(4, 5)).err(),
+                   Some(DimensionError::SizeMismatch { width: 5, height: 5, other_width: 4, other_height: 5 }));
     }
 
     #[test]
@@ -895,6 +4816,36 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn iter_stamped() {
+        let mut game = LifeGame::new(5, 5);
+        game.stamp(1, 2, &[(0, 0), (1, 0), (2, 0)]);
+        let cells: Vec<(usize, usize, bool, usize)> = game.iter_stamped(Some(true)).collect();
+        assert_eq!(cells, vec![(1,2,true,0), (2,2,true,0), (3,2,true,0)]);
+
+        game.evolution();
+        let cells: Vec<(usize, usize, bool, usize)> = game.iter_stamped(Some(true)).collect();
+        assert_eq!(cells, vec![(2,1,true,1), (2,2,true,1), (2,3,true,1)]);
+        assert_eq!(game.iter_stamped(None).count(), 25);
+    }
+
+    #[test]
+    fn iter_col_major() {
+        let mut game = LifeGame::new(2, 2);
+        game.set(1, 0, true);
+        game.set(0, 1, true);
+
+        let col_major: Vec<(usize, usize, bool)> = game.iter_col_major(None).collect();
+        assert_eq!(col_major, vec![(0,0,false), (0,1,true), (1,0,true), (1,1,false)]);
+        let row_major: Vec<(usize, usize, bool)> = game.iter(None).collect();
+        assert_ne!(col_major, row_major);
+
+        let live: Vec<(usize, usize, bool)> = game.iter_col_major(Some(true)).collect();
+        assert_eq!(live, vec![(0,1,true), (1,0,true)]);
+        let dead: Vec<(usize, usize, bool)> = game.iter_col_major(Some(false)).collect();
+        assert_eq!(dead, vec![(0,0,false), (1,1,false)]);
+    }
+
     #[test]
     fn iter_filter_live_true() {
         let mut game = LifeGame::new(2, 2);
@@ -919,6 +4870,130 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn iter_nth() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(2, 1, true);
+
+        let mut iter = game.iter(None);
+        assert_eq!(iter.nth(5), Some((2,1,true)));
+        assert_eq!(iter.next(), Some((0,2,false)));
+        assert_eq!(iter.nth(1), Some((2,2,false)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(game.iter(None).nth(9), None);
+        assert_eq!(game.iter(None).nth(usize::MAX), None);
+    }
+
+    #[test]
+    fn iter_nth_filtered() {
+        let mut game = LifeGame::new(3, 3);
+        game.set(0, 0, true);
+        game.set(2, 1, true);
+        game.set(1, 2, true);
+
+        let mut iter = game.iter(Some(true));
+        assert_eq!(iter.nth(1), Some((2,1,true)));
+        assert_eq!(iter.next(), Some((1,2,true)));
+        assert_eq!(game.iter(Some(true)).nth(3), None);
+    }
+
+    #[test]
+    fn iter_last() {
+        let mut game = LifeGame::new(3, 2);
+        game.set(2, 1, true);
+        game.set(0, 1, true);
+
+        assert_eq!(game.iter(None).last(), Some((2,1,true)));
+        assert_eq!(game.iter(Some(false)).last(), Some((1,1,false)));
+        assert_eq!(game.iter(Some(true)).last(), Some((2,1,true)));
+
+        let mut iter = game.iter(None);
+        iter.nth(5);
+        assert_eq!(iter.last(), None);
+    }
+
+    #[test]
+    fn iter_count() {
+        let mut game = LifeGame::new(4, 3);
+        game.set(0, 0, true);
+        game.set(3, 1, true);
+        game.set(2, 2, true);
+
+        for &live in [None, Some(true), Some(false)].iter() {
+            let walked = game.iter(live).fold(0, |count, _| count + 1);
+            assert_eq!(game.iter(live).count(), walked);
+        }
+        assert_eq!(game.iter(Some(true)).count(), 3);
+        assert_eq!(game.iter(Some(false)).count(), 9);
+
+        let mut iter = game.iter(Some(false));
+        iter.next();
+        assert_eq!(iter.count(), 8);
+        let mut iter = game.iter(None);
+        iter.nth(4);
+        assert_eq!(iter.count(), 7);
+    }
+
+    #[test]
+    fn neighbor_counts() {
+        /*
+         *  o o . o .      1 2 3 1 2
+         *  . . o . .  ->  2 4 3 3 2
+         *  . . o . .      0 2 1 2 0
+         *  . . . . .      2 3 3 2 2
+         */
+        let mut game = LifeGame::new(5, 4);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(3, 0, true);
+        game.set(2, 1, true);
+        game.set(2, 2, true);
+
+        assert_eq!(game.neighbor_counts(), vec![1, 2, 3, 1, 2,
+                                                2, 4, 3, 3, 2,
+                                                0, 2, 1, 2, 0,
+                                                2, 3, 3, 2, 2]);
+        assert_eq!(game.is_binary(), true);
+    }
+
+    #[test]
+    fn iter_counts_in() {
+        let mut game = LifeGame::new(5, 4);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(3, 0, true);
+        game.set(2, 1, true);
+        game.set(2, 2, true);
+
+        let mut iter = game.iter_counts_in(3..=3);
+        assert_eq!(iter.next(), Some((2,0,3)));
+        assert_eq!(iter.next(), Some((2,1,3)));
+        assert_eq!(iter.next(), Some((3,1,3)));
+        assert_eq!(iter.next(), Some((1,3,3)));
+        assert_eq!(iter.next(), Some((2,3,3)));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(game.iter_counts_in(4..=8).collect::<Vec<_>>(), vec![(1,1,4)]);
+    }
+
+    #[test]
+    fn neighbor_histogram() {
+        /*  1 1 . 1 .      1 2 3 1 2
+         *  . . 1 . .  ->  2 4 3 3 2
+         *  . . 1 . .      0 2 1 2 0
+         *  . . . . .      2 3 3 2 2
+         */
+        let mut game = LifeGame::new(5, 4);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(3, 0, true);
+        game.set(2, 1, true);
+        game.set(2, 2, true);
+
+        assert_eq!(game.neighbor_histogram(), [2, 3, 9, 5, 1, 0, 0, 0, 0]);
+        assert_eq!(game.num_cells(), 5);
+    }
+
     #[test]
     fn iter_as_u8() {
         /*
@@ -962,6 +5037,22 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn iter_as_u8_through_shared_reference() {
+        let mut game = LifeGame::new(5, 4);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.set(2, 2, true);
+        let before = game.to_bytes();
+
+        let shared = &game;
+        let counts: Vec<u8> = shared.iter_as_u8(Some(true)).map(|(_, _, count)| count).collect();
+        assert_eq!(counts, vec![1, 1, 0]);
+        assert_eq!(shared.num_cells(), 3);
+        assert_eq!(shared.is_binary(), true);
+        assert_eq!(game.to_bytes(), before);
+    }
+
     #[test]
     fn iter_as_u8_filter_live_true() {
         /*
@@ -0,0 +1,46 @@
+pub fn gosper_glider_gun() -> Vec<(usize, usize)> {
+    /* ........................o...........
+     * ......................o.o...........
+     * ............oo......oo............oo
+     * ...........o...o....oo............oo
+     * oo........o.....o...oo..............
+     * oo........o...o.oo....o.o...........
+     * ..........o.....o.......o...........
+     * ...........o...o....................
+     * ............oo......................
+     */
+    vec![
+        (24, 0),
+        (22, 1), (24, 1),
+        (12, 2), (13, 2), (20, 2), (21, 2), (34, 2), (35, 2),
+        (11, 3), (15, 3), (20, 3), (21, 3), (34, 3), (35, 3),
+        (0, 4), (1, 4), (10, 4), (16, 4), (20, 4), (21, 4),
+        (0, 5), (1, 5), (10, 5), (14, 5), (16, 5), (17, 5), (22, 5), (24, 5),
+        (10, 6), (16, 6), (24, 6),
+        (11, 7), (15, 7),
+        (12, 8), (13, 8),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lifegame::LifeGame;
+
+    #[test]
+    fn gosper_glider_gun_emits_gliders() {
+        let mut game = LifeGame::new(40, 40);
+        game.stamp(1, 1, &gosper_glider_gun());
+        assert_eq!(game.num_cells(), 36);
+
+        for _ in 0..30 {
+            game.evolution();
+        }
+        assert_eq!(game.num_cells(), 36 + 5);
+
+        for _ in 0..30 {
+            game.evolution();
+        }
+        assert_eq!(game.num_cells(), 36 + 5 * 2);
+    }
+}
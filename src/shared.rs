@@ -0,0 +1,102 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use lifegame::LifeGame;
+
+// A board shared between threads; every method locks for its whole call.
+// Needs the `send-callbacks` feature, which makes LifeGame `Send`.
+#[derive(Clone)]
+pub struct SharedLifeGame {
+    game: Arc<Mutex<LifeGame>>,
+}
+
+impl SharedLifeGame {
+    pub fn new(game: LifeGame) -> SharedLifeGame {
+        SharedLifeGame { game: Arc::new(Mutex::new(game)) }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LifeGame> {
+        // A panicking callback poisons the lock; the board itself is still consistent.
+        self.game.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn step(&self) -> usize {
+        let mut game = self.lock();
+        game.evolution();
+        game.generation()
+    }
+
+    pub fn set(&self, x: usize, y: usize, live: bool) {
+        self.lock().set(x, y, live);
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.lock().get(x, y)
+    }
+
+    pub fn generation(&self) -> usize {
+        self.lock().generation()
+    }
+
+    pub fn snapshot(&self) -> Vec<Vec<bool>> {
+        let game = self.lock();
+        let mut rows = vec![Vec::with_capacity(game.width()); game.height()];
+        for (_, y, live) in game.iter(None) {
+            rows[y].push(live);
+        }
+        rows
+    }
+
+    pub fn with<F, R>(&self, f: F) -> R
+        where F: FnOnce(&mut LifeGame) -> R {
+        f(&mut self.lock())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_one_board() {
+        let shared = SharedLifeGame::new(LifeGame::new(5, 5));
+        let other = shared.clone();
+        shared.set(1, 2, true);
+        shared.set(2, 2, true);
+        other.set(3, 2, true);
+
+        assert_eq!(other.step(), 1);
+        assert_eq!(shared.generation(), 1);
+        assert_eq!(shared.get(2, 1), true);
+        assert_eq!(shared.get(1, 2), false);
+
+        let snapshot = other.snapshot();
+        assert_eq!(snapshot.len(), 5);
+        assert_eq!(snapshot[1], vec![false, false, true, false, false]);
+        assert_eq!(snapshot[2], vec![false, false, true, false, false]);
+        assert_eq!(shared.with(|game| game.num_cells()), 3);
+    }
+
+    #[test]
+    fn step_on_another_thread() {
+        let shared = SharedLifeGame::new(LifeGame::new(5, 5));
+        shared.set(1, 2, true);
+        shared.set(2, 2, true);
+        shared.set(3, 2, true);
+
+        let stepper = shared.clone();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..10 {
+                stepper.step();
+            }
+        });
+        for _ in 0..10 {
+            let snapshot = shared.snapshot();
+            let live = snapshot.iter().flatten().filter(|&&live| live).count();
+            assert_eq!(live, 3);
+        }
+        handle.join().unwrap();
+        assert_eq!(shared.generation(), 10);
+        assert_eq!(shared.get(2, 1), false);
+        assert_eq!(shared.get(1, 2), true);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_set;
+use lifegame::LifeGame;
+
+// An unbounded board holding only the coordinates of the live cells.
+#[derive(Clone, Debug, Default)]
+pub struct SparseLifeGame {
+    generation: usize,
+    cells: HashSet<(i64, i64)>,
+}
+
+impl SparseLifeGame {
+    pub fn new() -> SparseLifeGame {
+        SparseLifeGame::default()
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        self.cells.contains(&(x, y))
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, live: bool) -> &Self {
+        if live {
+            self.cells.insert((x, y));
+        } else {
+            self.cells.remove(&(x, y));
+        }
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Only cells next to a live cell can change, so only they are counted.
+    pub fn evolution(&mut self) -> &Self {
+        let mut counts: HashMap<(i64, i64), u8> = HashMap::new();
+        for &(x, y) in self.cells.iter() {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx != 0) || (dy != 0) {
+                        *counts.entry((x + dx, y + dy)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        self.cells = counts
+            .into_iter()
+            .filter(|&(xy, count)| LifeGame::next_state(self.cells.contains(&xy), count as u16) > 0)
+            .map(|(xy, _)| xy)
+            .collect();
+        self.generation += 1;
+        self
+    }
+
+    // Live cells in no particular order.
+    pub fn iter(&self) -> SparseLifeGameIter<'_> {
+        SparseLifeGameIter { cells: self.cells.iter() }
+    }
+}
+
+pub struct SparseLifeGameIter<'a> {
+    cells: hash_set::Iter<'a, (i64, i64)>
+}
+
+impl<'a> Iterator for SparseLifeGameIter<'a> {
+    type Item = (i64, i64);
+    fn next(&mut self) -> Option<(i64, i64)> {
+        self.cells.next().cloned()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get() {
+        let mut game = SparseLifeGame::new();
+        game.set(-1_000_000, 5, true);
+        assert_eq!(game.get(-1_000_000, 5), true);
+        assert_eq!(game.get(0, 0), false);
+        assert_eq!(game.num_cells(), 1);
+
+        game.set(-1_000_000, 5, false);
+        assert_eq!(game.num_cells(), 0);
+    }
+
+    #[test]
+    fn blinker() {
+        let mut game = SparseLifeGame::new();
+        game.set(-1, 0, true);
+        game.set(0, 0, true);
+        game.set(1, 0, true);
+        game.evolution();
+
+        let mut cells: Vec<(i64, i64)> = game.iter().collect();
+        cells.sort();
+        assert_eq!(cells, vec![(0, -1), (0, 0), (0, 1)]);
+        assert_eq!(game.generation(), 1);
+    }
+
+    #[test]
+    fn glider_translates() {
+        /* .o.
+         * ..o
+         * ooo
+         */
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut game = SparseLifeGame::new();
+        for &(x, y) in glider.iter() {
+            game.set(x, y, true);
+        }
+        for _ in 0..40 {
+            game.evolution();
+        }
+
+        let mut cells: Vec<(i64, i64)> = game.iter().collect();
+        cells.sort();
+        let mut expected: Vec<(i64, i64)> = glider.iter().map(|&(x, y)| (x + 10, y + 10)).collect();
+        expected.sort();
+        assert_eq!(cells, expected);
+    }
+}
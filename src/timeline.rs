@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+use lifegame::LifeGame;
+
+// Records consecutive generations of a board, keeping at most `capacity`
+// of the latest ones. Frames are copy-on-write snapshots of the board.
+pub struct Timeline {
+    game: LifeGame,
+    frames: VecDeque<LifeGame>,
+    capacity: usize,
+    cursor: usize,
+}
+
+impl Timeline {
+    pub fn new(game: LifeGame, capacity: usize) -> Timeline {
+        if capacity == 0 {
+            panic!("Capacity must be not 0.");
+        }
+
+        let mut frames = VecDeque::new();
+        frames.push_back(game.snapshot());
+        Timeline { game, frames, capacity, cursor: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // The latest board, which `step` evolves.
+    pub fn game(&self) -> &LifeGame {
+        &self.game
+    }
+
+    // The frame the cursor is on.
+    pub fn current(&self) -> &LifeGame {
+        &self.frames[self.cursor]
+    }
+
+    pub fn step(&mut self) -> &LifeGame {
+        self.game.evolution();
+        self.frames.push_back(self.game.snapshot());
+        if self.frames.len() > self.capacity {
+            self.frames.pop_front();
+        }
+        self.cursor = self.frames.len() - 1;
+        self.current()
+    }
+
+    pub fn goto(&mut self, generation: usize) -> Option<&LifeGame> {
+        let first = self.frames[0].generation();
+        if (generation < first) || (generation - first >= self.frames.len()) {
+            return None;
+        }
+        self.cursor = generation - first;
+        Some(self.current())
+    }
+
+    // Like `goto`, but evolves the board when `generation` is past the
+    // latest frame. None when it is older than the recorded window, or
+    // when the board stops advancing before reaching it.
+    pub fn goto_generation(&mut self, generation: usize) -> Option<&LifeGame> {
+        while self.game.generation() < generation {
+            let before = self.game.generation();
+            self.step();
+            if self.game.generation() == before {
+                return None;
+            }
+        }
+        self.goto(generation)
+    }
+
+    // How many of the last `k` frames up to the cursor each cell was live in.
+    pub fn motion_trail(&self, k: usize) -> Vec<Vec<u8>> {
+        let current = self.current();
+        let mut trail = vec![vec![0u8; current.width()]; current.height()];
+        let first = (self.cursor + 1).saturating_sub(k);
+        for frame in self.frames.range(first..=self.cursor) {
+            for (x, y, _) in frame.iter(Some(true)) {
+                trail[y][x] = trail[y][x].saturating_add(1);
+            }
+        }
+        trail
+    }
+
+    pub fn population_csv(&self) -> String {
+        let mut csv = String::from("generation,population\n");
+        for frame in self.frames.iter() {
+            csv.push_str(&format!("{},{}\n", frame.generation(), frame.num_cells()));
+        }
+        csv
+    }
+
+    // Moves forward one frame, evolving the board past the latest one.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> &LifeGame {
+        if self.cursor + 1 < self.frames.len() {
+            self.cursor += 1;
+            self.current()
+        } else {
+            self.step()
+        }
+    }
+
+    pub fn prev(&mut self) -> Option<&LifeGame> {
+        if self.cursor == 0 {
+            return None;
+        }
+        self.cursor -= 1;
+        Some(self.current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glider() -> LifeGame {
+        let mut game = LifeGame::new(8, 8);
+        game.stamp(1, 1, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        game
+    }
+
+    fn cells(game: &LifeGame) -> Vec<(usize, usize)> {
+        game.iter(Some(true)).map(|(x, y, _)| (x, y)).collect()
+    }
+
+    #[test]
+    fn record_and_scrub() {
+        let mut timeline = Timeline::new(glider(), 100);
+        for _ in 0..10 {
+            timeline.step();
+        }
+        assert_eq!(timeline.len(), 11);
+        assert_eq!(timeline.game().generation(), 10);
+
+        let mut expected = glider();
+        for _ in 0..4 {
+            expected.evolution();
+        }
+        let frame = timeline.goto(4).unwrap();
+        assert_eq!(frame.generation(), 4);
+        assert_eq!(cells(frame), cells(&expected));
+
+        assert_eq!(timeline.prev().unwrap().generation(), 3);
+        assert_eq!(timeline.next().generation(), 4);
+        assert_eq!(timeline.current().generation(), 4);
+        assert_eq!(timeline.game().generation(), 10);
+
+        assert_eq!(timeline.goto(0).unwrap().generation(), 0);
+        assert!(timeline.prev().is_none());
+        assert!(timeline.goto(11).is_none());
+
+        timeline.goto(10);
+        assert_eq!(timeline.next().generation(), 11);
+        assert_eq!(timeline.len(), 12);
+    }
+
+    #[test]
+    fn goto_generation() {
+        let mut timeline = Timeline::new(glider(), 10);
+        let frame = timeline.goto_generation(5).unwrap();
+        assert_eq!(frame.generation(), 5);
+
+        let mut expected = glider();
+        for _ in 0..5 {
+            expected.evolution();
+        }
+        assert_eq!(cells(frame), cells(&expected));
+
+        assert_eq!(timeline.goto_generation(2).unwrap().generation(), 2);
+        assert_eq!(timeline.game().generation(), 5);
+
+        let mut timeline = Timeline::new(glider(), 3);
+        timeline.goto_generation(5);
+        assert!(timeline.goto_generation(2).is_none());
+        assert_eq!(timeline.current().generation(), 5);
+    }
+
+    #[test]
+    fn goto_generation_on_empty_board() {
+        let mut timeline = Timeline::new(LifeGame::empty(), 4);
+        assert!(timeline.goto_generation(3).is_none());
+        assert_eq!(timeline.game().generation(), 0);
+        assert_eq!(timeline.goto_generation(0).unwrap().generation(), 0);
+    }
+
+    #[test]
+    fn motion_trail() {
+        let mut timeline = Timeline::new(glider(), 10);
+        for _ in 0..4 {
+            timeline.step();
+        }
+        let trail = timeline.motion_trail(4);
+        assert_eq!(trail, vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 3, 0, 0, 0, 0],
+            vec![0, 1, 1, 3, 2, 0, 0, 0],
+            vec![0, 0, 4, 3, 1, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+        ]);
+
+        let total: usize = timeline.motion_trail(100).iter().flatten().map(|&n| n as usize).sum();
+        assert_eq!(total, 5 * 5);
+
+        timeline.goto(0);
+        let trail = timeline.motion_trail(4);
+        assert_eq!(trail.iter().flatten().filter(|&&n| n > 0).count(), 5);
+    }
+
+    #[test]
+    fn population_csv() {
+        /* Pre-block: 3 -> 4 -> 4 -> 4 */
+        let mut game = LifeGame::new(6, 6);
+        game.stamp(1, 1, &[(0, 0), (1, 0), (0, 1)]);
+        let mut timeline = Timeline::new(game, 10);
+        for _ in 0..3 {
+            timeline.step();
+        }
+        assert_eq!(timeline.population_csv(), "generation,population\n0,3\n1,4\n2,4\n3,4\n");
+    }
+
+    #[test]
+    fn capacity_drops_oldest() {
+        let mut timeline = Timeline::new(glider(), 5);
+        for _ in 0..10 {
+            timeline.step();
+        }
+        assert_eq!(timeline.len(), 5);
+        assert!(timeline.goto(5).is_none());
+        assert_eq!(timeline.goto(6).unwrap().generation(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be not 0.")]
+    fn capacity_0() {
+        Timeline::new(glider(), 0);
+    }
+}